@@ -0,0 +1,456 @@
+pub mod agent {
+    use std::{
+        collections::VecDeque,
+        f32::consts::TAU,
+        fmt,
+        fs::File,
+        io::{self, BufReader, ErrorKind, Read, Seek, SeekFrom},
+        net::{SocketAddr, TcpStream, ToSocketAddrs},
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    use tokio::{
+        io::AsyncWriteExt,
+        net::{TcpListener, TcpStream as AsyncTcpStream},
+        sync::broadcast::{self, error::RecvError},
+    };
+
+    use crate::{
+        encode_record, read_record, AlertEngine, AlertRule, SessionPlayer, Snapshot,
+        SnapshotCollector, SESSION_MAGIC,
+    };
+
+    pub const DEFAULT_AGENT_BIND: &str = "127.0.0.1:9102";
+
+    // En-tête envoyé par l'agent à la connexion (le dernier octet est la version du protocole)
+    pub const AGENT_MAGIC: &[u8; 8] = b"SMAGNT\0\x03";
+
+    // Snapshots gardés par le client pour chaque hôte (1 heure à 1 s)
+    pub const REMOTE_FRAMES: usize = 3600;
+
+    const CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+    const RETRY_DELAY: Duration = Duration::from_secs(2);
+    // Délai de lecture après lequel le client vérifie s'il doit s'arrêter
+    const READ_TIMEOUT: Duration = Duration::from_millis(500);
+    // Attente de nouveaux enregistrements dans une session suivie
+    const FOLLOW_POLL: Duration = Duration::from_millis(500);
+
+    #[derive(Debug, Clone)]
+    pub struct AgentConfig {
+        pub bind: SocketAddr,
+        pub interval: Duration,
+        // Snapshots récents envoyés à chaque nouveau client
+        pub backlog: usize,
+        // Nom d'hôte annoncé à la place de celui de la machine
+        pub hostname: Option<String>,
+        // Charge simulée (CPU, mémoire, disques, température): plusieurs agents locaux
+        // lancés avec des noms différents se comportent comme une flotte de machines
+        pub fake: bool,
+    }
+
+    // Agent: collecte un snapshot complet à chaque intervalle et le diffuse à tous les
+    // clients connectés. Protocole: AGENT_MAGIC puis des enregistrements de session
+    // (taille u32 little-endian + snapshot bincode), les plus anciens d'abord.
+    pub async fn serve_agent(config: AgentConfig) -> io::Result<()> {
+        let listener = TcpListener::bind(config.bind).await?;
+        let (sender, _) = broadcast::channel::<Arc<Vec<u8>>>(16);
+        let backlog = Arc::new(Mutex::new(VecDeque::new()));
+
+        {
+            let sender = sender.clone();
+            let backlog = backlog.clone();
+            tokio::spawn(async move {
+                let mut collector = SnapshotCollector::new();
+                // Laisser sysinfo mesurer l'utilisation CPU sur un premier intervalle
+                tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+                let mut ticker = tokio::time::interval(config.interval);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    ticker.tick().await;
+                    // La collecte lit /proc et lance `sensors`: hors du thread asynchrone
+                    let (returned, snapshot) = match tokio::task::spawn_blocking(move || {
+                        let snapshot = collector.collect();
+                        (collector, snapshot)
+                    })
+                    .await
+                    {
+                        Ok(result) => result,
+                        Err(e) => {
+                            eprintln!("Agent: collector stopped: {}", e);
+                            return;
+                        }
+                    };
+                    collector = returned;
+                    let mut snapshot = snapshot;
+                    if let Some(hostname) = &config.hostname {
+                        if let Some(computer) = snapshot.computer.as_mut() {
+                            computer.hostname = hostname.clone();
+                        }
+                    }
+                    if config.fake {
+                        simulate_load(&mut snapshot);
+                    }
+                    let record = match encode_record(&snapshot) {
+                        Ok(record) => Arc::new(record),
+                        Err(e) => {
+                            eprintln!("Agent: {}", e);
+                            continue;
+                        }
+                    };
+                    {
+                        let mut backlog = backlog.lock().unwrap();
+                        backlog.push_back(record.clone());
+                        while backlog.len() > config.backlog {
+                            backlog.pop_front();
+                        }
+                    }
+                    // Aucun client connecté n'est pas une erreur
+                    let _ = sender.send(record);
+                }
+            });
+        }
+
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            // Abonnement avant la copie de l'historique: un snapshot peut être reçu
+            // deux fois, le client ignore les doublons
+            let receiver = sender.subscribe();
+            let recent: Vec<Arc<Vec<u8>>> = backlog.lock().unwrap().iter().cloned().collect();
+            tokio::spawn(async move {
+                match handle_client(stream, recent, receiver).await {
+                    // Fermeture de l'interface cliente
+                    Err(e)
+                        if matches!(
+                            e.kind(),
+                            ErrorKind::BrokenPipe | ErrorKind::ConnectionReset
+                        ) => {}
+                    Err(e) => eprintln!("Agent: {}: {}", peer, e),
+                    Ok(()) => {}
+                }
+            });
+        }
+    }
+
+    async fn handle_client(
+        mut stream: AsyncTcpStream,
+        recent: Vec<Arc<Vec<u8>>>,
+        mut receiver: broadcast::Receiver<Arc<Vec<u8>>>,
+    ) -> io::Result<()> {
+        stream.set_nodelay(true)?;
+        stream.write_all(AGENT_MAGIC).await?;
+        for record in recent {
+            stream.write_all(&record).await?;
+        }
+        loop {
+            match receiver.recv().await {
+                Ok(record) => stream.write_all(&record).await?,
+                // Client trop lent: les snapshots manqués sont perdus
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Ok(()),
+            }
+        }
+    }
+
+    // Valeurs oscillant lentement, décalées d'un hôte à l'autre selon son nom
+    fn simulate_load(snapshot: &mut Snapshot) {
+        let hostname = snapshot
+            .computer
+            .as_ref()
+            .map(|computer| computer.hostname.clone())
+            .unwrap_or_default();
+        let phase = hostname.bytes().map(|byte| byte as f32).sum::<f32>();
+        let seconds = (snapshot.timestamp.timestamp_millis() % 3_600_000) as f32 / 1000.0;
+        let wave =
+            |period: f32, offset: f32| 0.5 + 0.5 * (TAU * seconds / period + phase + offset).sin();
+        let (cpu, memory, disk, temperature) = (
+            95.0 * wave(40.0, 0.0),
+            0.2 + 0.75 * wave(90.0, 1.0),
+            0.3 + 0.65 * wave(600.0, 2.0),
+            35.0 + 60.0 * wave(70.0, 3.0),
+        );
+        if let Some(snapshot_cpu) = snapshot.cpu.as_mut() {
+            snapshot_cpu.usage = cpu;
+            snapshot_cpu.temperature = temperature;
+            for (index, sensor) in snapshot_cpu.sensors.iter_mut().enumerate() {
+                sensor.temperature = temperature - index as f32 * 2.0;
+            }
+        }
+        if let Some(meminfo) = snapshot.meminfo.as_mut() {
+            // Le reste de la mémoire est réparti entre cache et mémoire libre
+            let used = (meminfo.mem_total as f32 * memory) as u64;
+            let rest = meminfo.mem_total.saturating_sub(used);
+            meminfo.buffers = 0;
+            meminfo.s_reclaimable = 0;
+            meminfo.cached = rest / 2;
+            meminfo.mem_free = rest - rest / 2;
+            meminfo.mem_available = rest;
+        }
+        for usage in snapshot.disks.iter_mut().flatten() {
+            usage.available_space = (usage.total_space as f32 * (1.0 - disk)) as u64;
+        }
+    }
+
+    // Origine des snapshots d'un hôte distant
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum RemoteSource {
+        // Agent TCP (host:port)
+        Agent(String),
+        // Session en cours d'enregistrement (commande `session`), lue au fur et à mesure
+        Session(PathBuf),
+    }
+
+    impl fmt::Display for RemoteSource {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                RemoteSource::Agent(address) => write!(f, "{}", address),
+                RemoteSource::Session(path) => write!(f, "{}", path.display()),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum RemoteStatus {
+        Connecting,
+        Connected,
+        Disconnected(String),
+    }
+
+    // Lecture bloquante interrompue quand le client est arrêté
+    struct StoppableStream<'a> {
+        stream: TcpStream,
+        running: &'a AtomicBool,
+    }
+
+    impl Read for StoppableStream<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            loop {
+                match self.stream.read(buf) {
+                    Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                        if !self.running.load(Ordering::Relaxed) {
+                            return Err(io::Error::new(ErrorKind::ConnectionAborted, "stopped"));
+                        }
+                    }
+                    result => return result,
+                }
+            }
+        }
+    }
+
+    // Hôte distant: un thread reçoit les snapshots de l'agent (avec reconnexion), le
+    // thread de l'interface les transfère dans un lecteur de session qui suit le direct
+    pub struct RemoteHost {
+        pub source: RemoteSource,
+        pub player: SessionPlayer,
+        pub status: Arc<Mutex<RemoteStatus>>,
+        // Règles d'alerte évaluées sur chaque snapshot reçu
+        pub alerts: AlertEngine,
+        inbox: Arc<Mutex<Vec<Snapshot>>>,
+        // Le lecteur contient un snapshot vide tant que rien n'est reçu
+        waiting: bool,
+        running: Arc<AtomicBool>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl RemoteHost {
+        pub fn connect(address: &str) -> RemoteHost {
+            RemoteHost::start(RemoteSource::Agent(address.to_string()))
+        }
+
+        pub fn follow(path: &Path) -> RemoteHost {
+            RemoteHost::start(RemoteSource::Session(path.to_path_buf()))
+        }
+
+        fn start(source: RemoteSource) -> RemoteHost {
+            let status = Arc::new(Mutex::new(RemoteStatus::Connecting));
+            let inbox = Arc::new(Mutex::new(Vec::new()));
+            let running = Arc::new(AtomicBool::new(true));
+            let handle = {
+                let source = source.clone();
+                let status = status.clone();
+                let inbox = inbox.clone();
+                let running = running.clone();
+                thread::spawn(move || {
+                    while running.load(Ordering::Relaxed) {
+                        *status.lock().unwrap() = RemoteStatus::Connecting;
+                        let result = match &source {
+                            RemoteSource::Agent(address) => {
+                                receive(address, &status, &inbox, &running)
+                            }
+                            RemoteSource::Session(path) => {
+                                follow_session(path, &status, &inbox, &running)
+                            }
+                        };
+                        let error = match result {
+                            Ok(()) => "connection closed by the agent".to_string(),
+                            Err(e) => e.to_string(),
+                        };
+                        *status.lock().unwrap() = RemoteStatus::Disconnected(error);
+                        let mut waited = Duration::ZERO;
+                        while running.load(Ordering::Relaxed) && waited < RETRY_DELAY {
+                            thread::sleep(Duration::from_millis(100));
+                            waited += Duration::from_millis(100);
+                        }
+                    }
+                })
+            };
+            let title = match &source {
+                RemoteSource::Agent(address) => format!("Agent: {}", address),
+                RemoteSource::Session(path) => format!("Session: {}", path.display()),
+            };
+            let mut player = SessionPlayer::from_frames(title, vec![Snapshot::collect(&[], 0)]);
+            player.live = true;
+            RemoteHost {
+                source,
+                player,
+                status,
+                alerts: AlertEngine::new(Vec::new()),
+                inbox,
+                waiting: true,
+                running,
+                handle: Some(handle),
+            }
+        }
+
+        // Transfère les snapshots reçus; renvoie vrai si le snapshot affiché a changé
+        pub fn sync(&mut self) -> bool {
+            let mut frames = std::mem::take(&mut *self.inbox.lock().unwrap());
+            frames.dedup_by_key(|frame| frame.timestamp);
+            if frames.is_empty() {
+                return false;
+            }
+            if !self.alerts.statuses.is_empty() {
+                for frame in &frames {
+                    self.alerts.evaluate(frame);
+                }
+            }
+            if self.waiting {
+                self.waiting = false;
+                let playing = self.player.playing;
+                self.player = SessionPlayer::from_frames(self.player.title.clone(), frames);
+                self.player.live = true;
+                self.player.playing = playing;
+                self.player.seek(self.player.frames.len() - 1);
+                return true;
+            }
+            self.player.append(frames, REMOTE_FRAMES)
+        }
+
+        // Dernier snapshot reçu, quelle que soit la position de lecture
+        pub fn latest(&self) -> Option<&Snapshot> {
+            if self.waiting {
+                return None;
+            }
+            self.player.frames.last()
+        }
+
+        // Les états repartent de zéro; les snapshots suivants sont évalués
+        pub fn set_rules(&mut self, rules: Vec<AlertRule>) {
+            self.alerts = AlertEngine::new(rules);
+        }
+
+        pub fn label(&self) -> String {
+            let status = match &*self.status.lock().unwrap() {
+                RemoteStatus::Connecting => "connecting".to_string(),
+                RemoteStatus::Connected => "connected".to_string(),
+                RemoteStatus::Disconnected(error) => format!("disconnected: {}", error),
+            };
+            format!("{} ({})", self.source, status)
+        }
+
+        pub fn stop(&mut self) {
+            self.running.store(false, Ordering::Relaxed);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    impl Drop for RemoteHost {
+        fn drop(&mut self) {
+            self.stop();
+        }
+    }
+
+    fn receive(
+        address: &str,
+        status: &Mutex<RemoteStatus>,
+        inbox: &Mutex<Vec<Snapshot>>,
+        running: &AtomicBool,
+    ) -> io::Result<()> {
+        let mut last_error = io::Error::new(ErrorKind::NotFound, "no address");
+        let mut connected = None;
+        for addr in address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                Ok(stream) => {
+                    connected = Some(stream);
+                    break;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        let stream = connected.ok_or(last_error)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = io::BufReader::new(StoppableStream { stream, running });
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != AGENT_MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not a system_monitor agent",
+            ));
+        }
+        *status.lock().unwrap() = RemoteStatus::Connected;
+        while running.load(Ordering::Relaxed) {
+            match read_record(&mut reader)? {
+                Some(snapshot) => inbox.lock().unwrap().push(snapshot),
+                None => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    // Suit une session en cours d'écriture: un enregistrement incomplet est relu
+    // quand l'enregistreur l'a terminé
+    fn follow_session(
+        path: &Path,
+        status: &Mutex<RemoteStatus>,
+        inbox: &Mutex<Vec<Snapshot>>,
+        running: &AtomicBool,
+    ) -> io::Result<()> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != SESSION_MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not a system_monitor session file",
+            ));
+        }
+        *status.lock().unwrap() = RemoteStatus::Connected;
+        while running.load(Ordering::Relaxed) {
+            let position = reader.stream_position()?;
+            match read_record(&mut reader) {
+                Ok(Some(snapshot)) => {
+                    inbox.lock().unwrap().push(snapshot);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => {}
+                Err(e) => return Err(e),
+            }
+            // Fichier recréé par un nouvel enregistrement: reprise depuis le début
+            if reader.get_ref().metadata()?.len() < position {
+                return Err(io::Error::other("session file truncated"));
+            }
+            reader.seek(SeekFrom::Start(position))?;
+            thread::sleep(FOLLOW_POLL);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,664 @@
+pub mod alert {
+    use std::{
+        fmt, fs, io,
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use chrono::{DateTime, Utc};
+    use imgui::Ui;
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        format_timestamp, parse_duration, NotificationSink, Notifier, SinkConfig, Snapshot,
+        SnapshotCollector,
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum AlertMetric {
+        Cpu,
+        Memory,
+        Swap,
+        // Capteur dont le label contient ce texte; sans capteur, la température CPU
+        Temperature(Option<String>),
+        Fan,
+        Disk(String),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Comparison {
+        Greater,
+        GreaterOrEqual,
+        Less,
+        LessOrEqual,
+    }
+
+    impl Comparison {
+        fn holds(&self, value: f64, threshold: f64) -> bool {
+            match self {
+                Comparison::Greater => value > threshold,
+                Comparison::GreaterOrEqual => value >= threshold,
+                Comparison::Less => value < threshold,
+                Comparison::LessOrEqual => value <= threshold,
+            }
+        }
+
+        // Seuil décalé pour rester actif tant que la valeur n'est pas revenue
+        // franchement de l'autre côté (hystérésis)
+        fn relaxed(&self, threshold: f64, hysteresis: f64) -> f64 {
+            match self {
+                Comparison::Greater | Comparison::GreaterOrEqual => threshold - hysteresis,
+                Comparison::Less | Comparison::LessOrEqual => threshold + hysteresis,
+            }
+        }
+
+        fn symbol(&self) -> &'static str {
+            match self {
+                Comparison::Greater => ">",
+                Comparison::GreaterOrEqual => ">=",
+                Comparison::Less => "<",
+                Comparison::LessOrEqual => "<=",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum AlertCondition {
+        Threshold {
+            metric: AlertMetric,
+            comparison: Comparison,
+            threshold: f64,
+        },
+        Process {
+            name: String,
+            running: bool,
+        },
+    }
+
+    impl AlertCondition {
+        // Analyse une expression comme "cpu > 90%", "temp \"Package id 0\" > 85°C",
+        // "disk / > 95%" ou "process nginx not running". Un suffixe "for 30s" est
+        // renvoyé séparément.
+        pub fn parse(expr: &str) -> Result<(AlertCondition, Option<Duration>), String> {
+            let mut tokens = tokenize(expr)?;
+            let mut duration = None;
+            if tokens.len() >= 2 && tokens[tokens.len() - 2].eq_ignore_ascii_case("for") {
+                duration = Some(parse_duration(&tokens[tokens.len() - 1])?);
+                tokens.truncate(tokens.len() - 2);
+            }
+            let mut tokens = tokens.into_iter().peekable();
+            let subject = tokens
+                .next()
+                .ok_or_else(|| "empty expression".to_string())?
+                .to_lowercase();
+
+            if subject == "process" {
+                let name = tokens
+                    .next()
+                    .ok_or_else(|| "missing process name".to_string())?;
+                let rest: Vec<String> = tokens.map(|t| t.to_lowercase()).collect();
+                let running = match rest.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+                    ["running"] | ["is", "running"] => true,
+                    ["not", "running"] | ["is", "not", "running"] => false,
+                    _ => return Err(format!("expected 'running' or 'not running' in '{}'", expr)),
+                };
+                return Ok((AlertCondition::Process { name, running }, duration));
+            }
+
+            let is_comparison = |t: &str| matches!(t, ">" | ">=" | "<" | "<=");
+            let metric = match subject.as_str() {
+                "cpu" => AlertMetric::Cpu,
+                "memory" | "mem" | "ram" => AlertMetric::Memory,
+                "swap" => AlertMetric::Swap,
+                "fan" => AlertMetric::Fan,
+                "temp" | "temperature" => {
+                    let sensor = match tokens.peek() {
+                        Some(t) if !is_comparison(t) => tokens.next(),
+                        _ => None,
+                    };
+                    AlertMetric::Temperature(sensor)
+                }
+                "disk" => AlertMetric::Disk(
+                    tokens
+                        .next()
+                        .ok_or_else(|| "missing disk mount point".to_string())?,
+                ),
+                other => return Err(format!("unknown metric '{}'", other)),
+            };
+            let comparison = match tokens.next().as_deref() {
+                Some(">") => Comparison::Greater,
+                Some(">=") => Comparison::GreaterOrEqual,
+                Some("<") => Comparison::Less,
+                Some("<=") => Comparison::LessOrEqual,
+                _ => return Err(format!("missing comparison operator in '{}'", expr)),
+            };
+            let value = tokens
+                .next()
+                .ok_or_else(|| format!("missing threshold in '{}'", expr))?;
+            let number = value.trim_end_matches(|c: char| !c.is_ascii_digit() && c != '.');
+            let threshold = number
+                .parse::<f64>()
+                .map_err(|_| format!("invalid threshold '{}'", value))?;
+            // Les unités séparées ("90 %", "85 °C", "2000 rpm") sont acceptées
+            if let Some(unit) = tokens.next() {
+                if !matches!(unit.to_lowercase().as_str(), "%" | "°c" | "c" | "rpm") {
+                    return Err(format!("unexpected '{}' in '{}'", unit, expr));
+                }
+            }
+            Ok((
+                AlertCondition::Threshold {
+                    metric,
+                    comparison,
+                    threshold,
+                },
+                duration,
+            ))
+        }
+    }
+
+    impl fmt::Display for AlertCondition {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                AlertCondition::Process { name, running } => write!(
+                    f,
+                    "process {} {}",
+                    name,
+                    if *running { "running" } else { "not running" }
+                ),
+                AlertCondition::Threshold {
+                    metric,
+                    comparison,
+                    threshold,
+                } => {
+                    let (name, unit) = match metric {
+                        AlertMetric::Cpu => ("cpu".to_string(), "%"),
+                        AlertMetric::Memory => ("memory".to_string(), "%"),
+                        AlertMetric::Swap => ("swap".to_string(), "%"),
+                        AlertMetric::Fan => ("fan".to_string(), " RPM"),
+                        AlertMetric::Temperature(None) => ("temp".to_string(), "°C"),
+                        AlertMetric::Temperature(Some(sensor)) => {
+                            (format!("temp \"{}\"", sensor), "°C")
+                        }
+                        AlertMetric::Disk(mount) => (format!("disk {}", mount), "%"),
+                    };
+                    write!(f, "{} {} {}{}", name, comparison.symbol(), threshold, unit)
+                }
+            }
+        }
+    }
+
+    fn tokenize(expr: &str) -> Result<Vec<String>, String> {
+        let mut tokens = Vec::new();
+        let mut chars = expr.chars().peekable();
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+            } else if c == '"' || c == '\'' {
+                chars.next();
+                let token: String = chars.by_ref().take_while(|&ch| ch != c).collect();
+                tokens.push(token);
+            } else if c == '>' || c == '<' {
+                chars.next();
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                    tokens.push(format!("{}=", c));
+                } else {
+                    tokens.push(c.to_string());
+                }
+            } else {
+                let mut token = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if ch.is_whitespace() || ch == '>' || ch == '<' {
+                        break;
+                    }
+                    token.push(ch);
+                    chars.next();
+                }
+                tokens.push(token);
+            }
+        }
+        Ok(tokens)
+    }
+
+    // Entrée du fichier de règles TOML
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct RuleConfig {
+        pub name: String,
+        pub expr: String,
+        #[serde(default, rename = "for")]
+        pub for_duration: Option<String>,
+        #[serde(default)]
+        pub hysteresis: f64,
+        #[serde(default)]
+        pub severity: Option<String>,
+        // Canaux de notification de la règle; tous si absent
+        #[serde(default)]
+        pub sinks: Option<Vec<String>>,
+    }
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct RulesFile {
+        #[serde(default, rename = "rule")]
+        pub rules: Vec<RuleConfig>,
+        #[serde(default, rename = "sink")]
+        pub sinks: Vec<SinkConfig>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct AlertRule {
+        pub name: String,
+        pub expr: String,
+        pub condition: AlertCondition,
+        pub duration: Duration,
+        pub hysteresis: f64,
+        pub severity: String,
+        pub sinks: Option<Vec<String>>,
+    }
+
+    impl AlertRule {
+        pub fn from_config(config: &RuleConfig) -> Result<AlertRule, String> {
+            let (condition, inline_duration) = AlertCondition::parse(&config.expr)
+                .map_err(|e| format!("rule '{}': {}", config.name, e))?;
+            let duration = match &config.for_duration {
+                Some(duration) => parse_duration(duration)
+                    .map_err(|e| format!("rule '{}': {}", config.name, e))?,
+                None => inline_duration.unwrap_or_default(),
+            };
+            Ok(AlertRule {
+                name: config.name.clone(),
+                expr: config.expr.clone(),
+                condition,
+                duration,
+                hysteresis: config.hysteresis.abs(),
+                severity: config
+                    .severity
+                    .clone()
+                    .unwrap_or_else(|| "warning".to_string()),
+                sinks: config.sinks.clone(),
+            })
+        }
+    }
+
+    pub struct RuleSet {
+        pub rules: Vec<AlertRule>,
+        pub sinks: Vec<NotificationSink>,
+    }
+
+    pub fn parse_rules(content: &str) -> Result<RuleSet, String> {
+        let file: RulesFile = toml::from_str(content).map_err(|e| e.to_string())?;
+        let rules = file
+            .rules
+            .iter()
+            .map(AlertRule::from_config)
+            .collect::<Result<Vec<_>, _>>()?;
+        let sinks = file
+            .sinks
+            .iter()
+            .map(NotificationSink::from_config)
+            .collect::<Result<Vec<_>, _>>()?;
+        for rule in &rules {
+            for name in rule.sinks.iter().flatten() {
+                if !sinks.iter().any(|sink| &sink.name == name) {
+                    return Err(format!("rule '{}': unknown sink '{}'", rule.name, name));
+                }
+            }
+        }
+        Ok(RuleSet { rules, sinks })
+    }
+
+    pub fn load_rules<P: AsRef<Path>>(path: P) -> io::Result<RuleSet> {
+        let content = fs::read_to_string(&path)?;
+        parse_rules(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum AlertState {
+        Ok,
+        Pending,
+        Firing,
+        Resolved,
+    }
+
+    impl AlertState {
+        pub fn label(&self) -> &'static str {
+            match self {
+                AlertState::Ok => "OK",
+                AlertState::Pending => "Pending",
+                AlertState::Firing => "Firing",
+                AlertState::Resolved => "Resolved",
+            }
+        }
+
+        pub fn color(&self) -> [f32; 4] {
+            match self {
+                AlertState::Ok => [0.0, 1.0, 0.0, 1.0],
+                AlertState::Pending => [1.0, 1.0, 0.0, 1.0],
+                AlertState::Firing => [1.0, 0.0, 0.0, 1.0],
+                AlertState::Resolved => [0.4, 0.7, 1.0, 1.0],
+            }
+        }
+
+        fn is_active(&self) -> bool {
+            matches!(self, AlertState::Pending | AlertState::Firing)
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct AlertStatus {
+        pub rule: AlertRule,
+        pub state: AlertState,
+        pub value: Option<f64>,
+        // Début de la condition (état Pending) et dernier changement d'état
+        pub active_since: Option<DateTime<Utc>>,
+        pub changed_at: DateTime<Utc>,
+    }
+
+    // Changement d'état d'une règle, transmis aux notifications
+    #[derive(Debug, Clone, Serialize)]
+    pub struct AlertTransition {
+        pub rule: String,
+        pub expr: String,
+        pub severity: String,
+        pub from: AlertState,
+        pub to: AlertState,
+        pub value: Option<f64>,
+        pub timestamp: DateTime<Utc>,
+        pub hostname: String,
+        #[serde(skip)]
+        pub sinks: Option<Vec<String>>,
+    }
+
+    pub struct AlertEngine {
+        pub statuses: Vec<AlertStatus>,
+    }
+
+    impl AlertEngine {
+        pub fn new(rules: Vec<AlertRule>) -> AlertEngine {
+            let now = Utc::now();
+            AlertEngine {
+                statuses: rules
+                    .into_iter()
+                    .map(|rule| AlertStatus {
+                        rule,
+                        state: AlertState::Ok,
+                        value: None,
+                        active_since: None,
+                        changed_at: now,
+                    })
+                    .collect(),
+            }
+        }
+
+        // Évalue toutes les règles; l'horloge est celle du snapshot (relecture possible)
+        pub fn evaluate(&mut self, snapshot: &Snapshot) -> Vec<AlertTransition> {
+            let now = snapshot.timestamp;
+            let hostname = snapshot
+                .computer
+                .as_ref()
+                .map(|c| c.hostname.clone())
+                .unwrap_or_default();
+            let mut transitions = Vec::new();
+            for status in self.statuses.iter_mut() {
+                let (holds, value) = evaluate_condition(
+                    &status.rule.condition,
+                    status.rule.hysteresis,
+                    status.state.is_active(),
+                    snapshot,
+                );
+                status.value = value;
+                let next = match (status.state, holds) {
+                    (AlertState::Ok | AlertState::Resolved, true) => {
+                        status.active_since = Some(now);
+                        if status.rule.duration.is_zero() {
+                            AlertState::Firing
+                        } else {
+                            AlertState::Pending
+                        }
+                    }
+                    (AlertState::Pending, true) => {
+                        let since = status.active_since.unwrap_or(now);
+                        let elapsed = (now - since).to_std().unwrap_or_default();
+                        if elapsed >= status.rule.duration {
+                            AlertState::Firing
+                        } else {
+                            AlertState::Pending
+                        }
+                    }
+                    (AlertState::Firing, true) => AlertState::Firing,
+                    (AlertState::Pending, false) => {
+                        status.active_since = None;
+                        AlertState::Ok
+                    }
+                    (AlertState::Firing, false) => {
+                        status.active_since = None;
+                        AlertState::Resolved
+                    }
+                    (state, false) => state,
+                };
+                if next != status.state {
+                    transitions.push(AlertTransition {
+                        rule: status.rule.name.clone(),
+                        expr: status.rule.expr.clone(),
+                        severity: status.rule.severity.clone(),
+                        from: status.state,
+                        to: next,
+                        value,
+                        timestamp: now,
+                        hostname: hostname.clone(),
+                        sinks: status.rule.sinks.clone(),
+                    });
+                    status.state = next;
+                    status.changed_at = now;
+                }
+            }
+            transitions
+        }
+
+        pub fn firing_count(&self) -> usize {
+            self.statuses
+                .iter()
+                .filter(|status| status.state == AlertState::Firing)
+                .count()
+        }
+    }
+
+    // Renvoie (condition vérifiée, valeur observée)
+    fn evaluate_condition(
+        condition: &AlertCondition,
+        hysteresis: f64,
+        active: bool,
+        snapshot: &Snapshot,
+    ) -> (bool, Option<f64>) {
+        match condition {
+            AlertCondition::Process { name, running } => {
+                let Some(processes) = snapshot.processes.as_ref() else {
+                    return (false, None);
+                };
+                let count = processes.iter().filter(|p| &p.name == name).count();
+                ((count > 0) == *running, Some(count as f64))
+            }
+            AlertCondition::Threshold {
+                metric,
+                comparison,
+                threshold,
+            } => match metric_value(metric, snapshot) {
+                Some(value) => {
+                    let threshold = if active {
+                        comparison.relaxed(*threshold, hysteresis)
+                    } else {
+                        *threshold
+                    };
+                    (comparison.holds(value, threshold), Some(value))
+                }
+                None => (false, None),
+            },
+        }
+    }
+
+    pub fn metric_value(metric: &AlertMetric, snapshot: &Snapshot) -> Option<f64> {
+        match metric {
+            AlertMetric::Cpu => snapshot.cpu.as_ref().map(|cpu| cpu.usage as f64),
+            AlertMetric::Memory => match (&snapshot.meminfo, &snapshot.memory) {
+                (Some(meminfo), _) if meminfo.mem_total > 0 => Some(meminfo.used_percent() as f64),
+                (_, Some(memory)) if memory.ram.total_ram > 0 => {
+                    Some(memory.ram.used_ram as f64 * 100.0 / memory.ram.total_ram as f64)
+                }
+                _ => None,
+            },
+            AlertMetric::Swap => snapshot
+                .meminfo
+                .as_ref()
+                .map(|meminfo| meminfo.swap_used_percent() as f64),
+            AlertMetric::Temperature(None) => {
+                snapshot.cpu.as_ref().map(|cpu| cpu.temperature as f64)
+            }
+            AlertMetric::Temperature(Some(sensor)) => {
+                let sensor = sensor.to_lowercase();
+                snapshot.cpu.as_ref().and_then(|cpu| {
+                    cpu.sensors
+                        .iter()
+                        .find(|reading| reading.label.to_lowercase().contains(&sensor))
+                        .map(|reading| reading.temperature as f64)
+                })
+            }
+            AlertMetric::Fan => snapshot
+                .cpu
+                .as_ref()
+                .and_then(|cpu| cpu.fans.first().and_then(|fan| fan.rpm))
+                .map(|rpm| rpm as f64),
+            AlertMetric::Disk(mount) => snapshot.disks.as_ref().and_then(|disks| {
+                disks
+                    .iter()
+                    .find(|disk| &disk.mount_point == mount)
+                    .map(|disk| disk.used_percent() as f64)
+            }),
+        }
+    }
+
+    // Évalue les règles en continu sur le système local dans un thread dédié
+    pub struct AlertMonitor {
+        pub rules_path: Option<PathBuf>,
+        pub engine: Arc<Mutex<AlertEngine>>,
+        pub notifier: Arc<Mutex<Notifier>>,
+        pub error: Option<String>,
+    }
+
+    impl AlertMonitor {
+        pub fn load(rules_path: Option<PathBuf>) -> AlertMonitor {
+            let mut monitor = AlertMonitor {
+                rules_path,
+                engine: Arc::new(Mutex::new(AlertEngine::new(Vec::new()))),
+                notifier: Arc::new(Mutex::new(Notifier::spawn(Vec::new()))),
+                error: None,
+            };
+            monitor.reset();
+            monitor
+        }
+
+        // Recharge les règles et remet tous les états à OK (ex: retour arrière en relecture)
+        pub fn reset(&mut self) {
+            let (rules, sinks) = match &self.rules_path {
+                Some(path) => match load_rules(path) {
+                    Ok(set) => (set.rules, set.sinks),
+                    Err(e) => {
+                        self.error = Some(format!("{}: {}", path.display(), e));
+                        (Vec::new(), Vec::new())
+                    }
+                },
+                None => (Vec::new(), Vec::new()),
+            };
+            *self.engine.lock().unwrap() = AlertEngine::new(rules);
+            *self.notifier.lock().unwrap() = Notifier::spawn(sinks);
+        }
+
+        // Évaluation périodique du système local, avec envoi des notifications
+        pub fn start(&self, interval: Duration) {
+            if self.engine.lock().unwrap().statuses.is_empty() {
+                return;
+            }
+            let engine = self.engine.clone();
+            let notifier = self.notifier.clone();
+            thread::spawn(move || {
+                let mut collector = SnapshotCollector::new();
+                loop {
+                    thread::sleep(interval);
+                    let snapshot = collector.collect();
+                    let transitions = engine.lock().unwrap().evaluate(&snapshot);
+                    notifier.lock().unwrap().notify(&transitions);
+                }
+            });
+        }
+
+        // Règles chargées, à évaluer aussi sur les hôtes distants
+        pub fn rules(&self) -> Vec<AlertRule> {
+            let engine = self.engine.lock().unwrap();
+            engine.statuses.iter().map(|status| status.rule.clone()).collect()
+        }
+
+        // En relecture: pas de notification pour des alertes passées
+        pub fn evaluate(&self, snapshot: &Snapshot) -> Vec<AlertTransition> {
+            self.engine.lock().unwrap().evaluate(snapshot)
+        }
+    }
+
+    pub fn draw_alerts_table(ui: &Ui, monitor: &AlertMonitor) {
+        if let Some(error) = &monitor.error {
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("Rules error: {}", error));
+        }
+        let engine = monitor.engine.lock().unwrap();
+        match &monitor.rules_path {
+            Some(path) => ui.text(format!("Rules: {}", path.display())),
+            None => ui.text("No rules file (use --rules <FILE>)"),
+        }
+        ui.text(format!(
+            "Rules: {}   Firing: {}",
+            engine.statuses.len(),
+            engine.firing_count()
+        ));
+        let notifier = monitor.notifier.lock().unwrap();
+        if !notifier.sink_names.is_empty() {
+            let status = notifier.status.lock().unwrap().clone();
+            ui.text(format!(
+                "Sinks: {}   Sent: {}   Suppressed: {}   Failed: {}",
+                notifier.sink_names.join(", "),
+                status.sent,
+                status.suppressed,
+                status.failed
+            ));
+            if let Some(error) = status.last_error {
+                ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("Last error: {}", error));
+            }
+        }
+        ui.separator();
+
+        ui.columns(5, "AlertColumns", true);
+        for header in ["Rule", "Condition", "State", "Value", "Since"] {
+            ui.text(header);
+            ui.next_column();
+        }
+        ui.separator();
+        for status in &engine.statuses {
+            ui.text(&status.rule.name);
+            ui.next_column();
+            let mut condition = status.rule.condition.to_string();
+            if !status.rule.duration.is_zero() {
+                condition.push_str(&format!(" for {}s", status.rule.duration.as_secs()));
+            }
+            ui.text(condition);
+            ui.next_column();
+            ui.text_colored(status.state.color(), status.state.label());
+            ui.next_column();
+            match status.value {
+                Some(value) => ui.text(format!("{:.2}", value)),
+                None => ui.text("N/A"),
+            }
+            ui.next_column();
+            ui.text(format_timestamp(status.changed_at.into()));
+            ui.next_column();
+        }
+        ui.columns(1, "", false);
+    }
+}
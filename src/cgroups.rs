@@ -0,0 +1,490 @@
+pub mod cgroup {
+    use std::{
+        collections::HashMap,
+        fs, io,
+        path::{Path, PathBuf},
+        time::Instant,
+    };
+
+    use imgui::{TreeNodeFlags, Ui};
+
+    use crate::{convert_bytes_to_any, ProcessInfo};
+
+    pub const DEFAULT_CGROUP_ROOT: &str = "/sys/fs/cgroup";
+    pub const DEFAULT_PROC_ROOT: &str = "/proc";
+
+    // Compteurs bruts d'un cgroup (absents si le contrôleur n'est pas activé)
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct CgroupStats {
+        // cpu.stat, en microsecondes
+        pub cpu_usage_usec: Option<u64>,
+        pub memory_current: Option<u64>,
+        // None: pas de limite ("max")
+        pub memory_max: Option<u64>,
+        // io.stat, cumulé sur tous les périphériques
+        pub io_read_bytes: Option<u64>,
+        pub io_write_bytes: Option<u64>,
+        pub pids_current: Option<u64>,
+    }
+
+    impl CgroupStats {
+        pub fn read(dir: &Path) -> CgroupStats {
+            let read = |name: &str| fs::read_to_string(dir.join(name)).ok();
+            let cpu_usage_usec = read("cpu.stat").and_then(|content| {
+                content.lines().find_map(|line| {
+                    line.strip_prefix("usage_usec ")
+                        .and_then(|value| value.trim().parse().ok())
+                })
+            });
+            let (io_read_bytes, io_write_bytes) = match read("io.stat") {
+                Some(content) => {
+                    let (read_bytes, write_bytes) = parse_io_stat(&content);
+                    (Some(read_bytes), Some(write_bytes))
+                }
+                None => (None, None),
+            };
+            CgroupStats {
+                cpu_usage_usec,
+                memory_current: read("memory.current").and_then(|value| value.trim().parse().ok()),
+                memory_max: read("memory.max").and_then(|value| value.trim().parse().ok()),
+                io_read_bytes,
+                io_write_bytes,
+                pids_current: read("pids.current").and_then(|value| value.trim().parse().ok()),
+            }
+        }
+
+        // Mémoire utilisée en % de la limite
+        pub fn memory_percent(&self) -> Option<f32> {
+            match (self.memory_current, self.memory_max) {
+                (Some(current), Some(max)) if max > 0 => Some(current as f32 * 100.0 / max as f32),
+                _ => None,
+            }
+        }
+    }
+
+    // Lignes "8:0 rbytes=1 wbytes=2 rios=3 wios=4 ..." de io.stat
+    pub fn parse_io_stat(content: &str) -> (u64, u64) {
+        let mut totals = (0, 0);
+        for field in content.split_whitespace() {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            let value: u64 = value.parse().unwrap_or(0);
+            match key {
+                "rbytes" => totals.0 += value,
+                "wbytes" => totals.1 += value,
+                _ => {}
+            }
+        }
+        totals
+    }
+
+    // Chemin du cgroup v2 d'un processus (ligne "0::/chemin" de /proc/<pid>/cgroup)
+    pub fn parse_proc_cgroup(content: &str) -> Option<String> {
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("0::"))
+            .map(|path| path.trim().to_string())
+    }
+
+    pub fn read_process_cgroup(proc_root: &Path, pid: usize) -> Option<String> {
+        let content = fs::read_to_string(proc_root.join(pid.to_string()).join("cgroup")).ok()?;
+        parse_proc_cgroup(&content)
+    }
+
+    // Nom lisible d'un cgroup: conteneur Docker/Podman/LXC, sinon le dernier segment
+    pub fn cgroup_label(path: &str) -> String {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        let short_id = |id: &str| id.chars().take(12).collect::<String>();
+        for (prefix, runtime) in [
+            ("docker-", "docker"),
+            ("libpod-", "podman"),
+            ("cri-containerd-", "containerd"),
+        ] {
+            if let Some(id) = name
+                .strip_prefix(prefix)
+                .and_then(|id| id.strip_suffix(".scope"))
+            {
+                return format!("{} {}", runtime, short_id(id));
+            }
+        }
+        // cgroupfs de Docker: /docker/<id>
+        if let Some(id) = path.strip_prefix("/docker/") {
+            return format!("docker {}", short_id(id));
+        }
+        if let Some(name) = name.strip_prefix("lxc.payload.") {
+            return format!("lxc {}", name);
+        }
+        if name.is_empty() {
+            "/".to_string()
+        } else {
+            name.to_string()
+        }
+    }
+
+    pub fn is_container(path: &str) -> bool {
+        let label = cgroup_label(path);
+        ["docker ", "podman ", "containerd ", "lxc "]
+            .iter()
+            .any(|runtime| label.starts_with(runtime))
+            || path.starts_with("/machine.slice/")
+    }
+
+    // Racine effective: en mode hybride (v1 + v2), la hiérarchie v2 est sous unified/
+    pub fn cgroup_hierarchy(root: &Path) -> io::Result<PathBuf> {
+        if root.join("cgroup.controllers").exists() {
+            return Ok(root.to_path_buf());
+        }
+        let unified = root.join("unified");
+        if unified.join("cgroup.controllers").exists() {
+            return Ok(unified);
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{}: not a cgroup v2 hierarchy", root.display()),
+        ))
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CgroupNode {
+        // Chemin relatif à la racine, ex: /system.slice/docker-<id>.scope
+        pub path: String,
+        pub name: String,
+        pub stats: CgroupStats,
+        // Calculés par rapport à la lecture précédente
+        pub cpu_percent: Option<f32>,
+        pub io_read_rate: Option<f64>,
+        pub io_write_rate: Option<f64>,
+        // Processus directement rattachés à ce cgroup
+        pub pids: Vec<usize>,
+        pub children: Vec<CgroupNode>,
+    }
+
+    impl CgroupNode {
+        // Processus du cgroup et de ses descendants
+        pub fn total_pids(&self) -> usize {
+            self.pids.len()
+                + self
+                    .children
+                    .iter()
+                    .map(CgroupNode::total_pids)
+                    .sum::<usize>()
+        }
+
+        pub fn descendants(&self) -> Vec<&CgroupNode> {
+            let mut nodes = Vec::new();
+            for child in &self.children {
+                nodes.push(child);
+                nodes.extend(child.descendants());
+            }
+            nodes
+        }
+    }
+
+    // Hiérarchie cgroup v2 lue sous `root`, avec les processus de `proc_root`
+    pub struct CgroupReader {
+        pub root: PathBuf,
+        pub proc_root: PathBuf,
+        previous: HashMap<String, CgroupStats>,
+        last_read: Option<Instant>,
+    }
+
+    impl CgroupReader {
+        pub fn new<P: Into<PathBuf>, Q: Into<PathBuf>>(root: P, proc_root: Q) -> CgroupReader {
+            CgroupReader {
+                root: root.into(),
+                proc_root: proc_root.into(),
+                previous: HashMap::new(),
+                last_read: None,
+            }
+        }
+
+        pub fn read(&mut self) -> io::Result<CgroupNode> {
+            let hierarchy = cgroup_hierarchy(&self.root)?;
+            let now = Instant::now();
+            let elapsed = self
+                .last_read
+                .map(|last| now.duration_since(last).as_secs_f64())
+                .filter(|elapsed| *elapsed > 0.0);
+            let mut pids = self.map_processes();
+            let mut current = HashMap::new();
+            let root = self.read_node(&hierarchy, "/", elapsed, &mut pids, &mut current);
+            self.previous = current;
+            self.last_read = Some(now);
+            Ok(root)
+        }
+
+        // Processus groupés par chemin de cgroup
+        pub fn map_processes(&self) -> HashMap<String, Vec<usize>> {
+            let mut map: HashMap<String, Vec<usize>> = HashMap::new();
+            let Ok(entries) = fs::read_dir(&self.proc_root) else {
+                return map;
+            };
+            for entry in entries.filter_map(Result::ok) {
+                let Some(pid) = entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.parse::<usize>().ok())
+                else {
+                    continue;
+                };
+                if let Some(path) = read_process_cgroup(&self.proc_root, pid) {
+                    map.entry(path).or_default().push(pid);
+                }
+            }
+            for pids in map.values_mut() {
+                pids.sort_unstable();
+            }
+            map
+        }
+
+        fn read_node(
+            &self,
+            dir: &Path,
+            path: &str,
+            elapsed: Option<f64>,
+            pids: &mut HashMap<String, Vec<usize>>,
+            current: &mut HashMap<String, CgroupStats>,
+        ) -> CgroupNode {
+            let stats = CgroupStats::read(dir);
+            let previous = self.previous.get(path);
+            let rate = |now: Option<u64>, before: Option<u64>| match (now, before, elapsed) {
+                (Some(now), Some(before), Some(elapsed)) => {
+                    Some(now.saturating_sub(before) as f64 / elapsed)
+                }
+                _ => None,
+            };
+            let cpu_percent = rate(
+                stats.cpu_usage_usec,
+                previous.and_then(|previous| previous.cpu_usage_usec),
+            )
+            .map(|usec_per_second| (usec_per_second / 10_000.0) as f32);
+            let io_read_rate = rate(
+                stats.io_read_bytes,
+                previous.and_then(|previous| previous.io_read_bytes),
+            );
+            let io_write_rate = rate(
+                stats.io_write_bytes,
+                previous.and_then(|previous| previous.io_write_bytes),
+            );
+
+            let mut children: Vec<CgroupNode> = fs::read_dir(dir)
+                .map(|entries| {
+                    entries
+                        .filter_map(Result::ok)
+                        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+                        .map(|entry| {
+                            let name = entry.file_name().to_string_lossy().to_string();
+                            let child_path = if path == "/" {
+                                format!("/{}", name)
+                            } else {
+                                format!("{}/{}", path, name)
+                            };
+                            self.read_node(&entry.path(), &child_path, elapsed, pids, current)
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            children.sort_by(|a, b| a.name.cmp(&b.name));
+            current.insert(path.to_string(), stats.clone());
+            CgroupNode {
+                path: path.to_string(),
+                name: cgroup_label(path),
+                stats,
+                cpu_percent,
+                io_read_rate,
+                io_write_rate,
+                pids: pids.remove(path).unwrap_or_default(),
+                children,
+            }
+        }
+    }
+
+    // État de la fenêtre "Containers / Slices"
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct CgroupView {
+        // Conteneurs seulement, triés par CPU, au lieu de l'arborescence complète
+        pub containers_only: bool,
+        // Cgroup dont les processus sont listés
+        pub selected: Option<String>,
+    }
+
+    impl CgroupView {
+        pub fn new() -> CgroupView {
+            Self::default()
+        }
+    }
+
+    const COLUMNS: [&str; 7] = [
+        "Cgroup",
+        "CPU",
+        "Memory",
+        "Limit",
+        "I/O Read",
+        "I/O Write",
+        "Pids",
+    ];
+
+    pub fn draw_cgroups(
+        ui: &Ui,
+        tree: Result<&CgroupNode, &str>,
+        processes: &[ProcessInfo],
+        view: &mut CgroupView,
+    ) {
+        let root = match tree {
+            Ok(root) => root,
+            Err(error) => {
+                ui.text_colored(
+                    [1.0, 0.3, 0.3, 1.0],
+                    format!("Cgroups unavailable: {}", error),
+                );
+                return;
+            }
+        };
+        let containers: Vec<&CgroupNode> = root
+            .descendants()
+            .into_iter()
+            .filter(|node| is_container(&node.path))
+            .collect();
+        ui.text(format!(
+            "Cgroups: {}   Containers: {}",
+            root.descendants().len(),
+            containers.len()
+        ));
+        ui.same_line();
+        ui.checkbox("Containers only", &mut view.containers_only);
+        ui.separator();
+
+        ui.columns(COLUMNS.len() as i32, "CgroupColumns", true);
+        for header in COLUMNS {
+            ui.text(header);
+            ui.next_column();
+        }
+        ui.separator();
+        if view.containers_only {
+            let mut containers = containers;
+            containers.sort_by(|a, b| {
+                b.cpu_percent
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.cpu_percent.unwrap_or(0.0))
+            });
+            for node in containers {
+                draw_row(ui, node, false, view);
+            }
+        } else {
+            draw_node(ui, root, view);
+        }
+        ui.columns(1, "", false);
+
+        // Processus du cgroup sélectionné
+        let Some(selected) = view.selected.clone() else {
+            return;
+        };
+        let Some(node) = std::iter::once(root)
+            .chain(root.descendants())
+            .find(|node| node.path == selected)
+        else {
+            view.selected = None;
+            return;
+        };
+        ui.separator();
+        ui.text(format!("Processes in {}:", node.path));
+        if node.pids.is_empty() {
+            ui.text_disabled("No process directly in this cgroup");
+        }
+        for pid in &node.pids {
+            match processes.iter().find(|process| process.pid == *pid) {
+                Some(process) => ui.text(format!(
+                    "{:>8}  {:<24} CPU {:.2}%  Memory {:.2}%",
+                    pid, process.name, process.cpu_usage, process.memory_usage
+                )),
+                None => ui.text(format!("{:>8}", pid)),
+            }
+        }
+    }
+
+    fn draw_node(ui: &Ui, node: &CgroupNode, view: &mut CgroupView) {
+        if let Some(_token) = draw_row(ui, node, true, view) {
+            for child in &node.children {
+                draw_node(ui, child, view);
+            }
+        }
+    }
+
+    fn draw_row<'ui>(
+        ui: &'ui Ui,
+        node: &CgroupNode,
+        tree: bool,
+        view: &mut CgroupView,
+    ) -> Option<imgui::TreeNodeToken<'ui>> {
+        let selected = view.selected.as_deref() == Some(node.path.as_str());
+        let mut flags = TreeNodeFlags::OPEN_ON_ARROW | TreeNodeFlags::SPAN_AVAIL_WIDTH;
+        if !tree || node.children.is_empty() {
+            flags |= TreeNodeFlags::LEAF;
+        }
+        if selected {
+            flags |= TreeNodeFlags::SELECTED;
+        }
+        if node.path == "/" {
+            flags |= TreeNodeFlags::DEFAULT_OPEN;
+        }
+        let token = ui
+            .tree_node_config(format!("{}##{}", node.name, node.path))
+            .flags(flags)
+            .push();
+        if ui.is_item_clicked() && !ui.is_item_toggled_open() {
+            view.selected = if selected {
+                None
+            } else {
+                Some(node.path.clone())
+            };
+        }
+        if ui.is_item_hovered() {
+            ui.tooltip_text(&node.path);
+        }
+        ui.next_column();
+        let text_or_dash =
+            |value: Option<String>| ui.text(value.unwrap_or_else(|| "-".to_string()));
+        text_or_dash(node.cpu_percent.map(|cpu| format!("{:.1}%", cpu)));
+        ui.next_column();
+        text_or_dash(
+            node.stats
+                .memory_current
+                .map(|current| match node.stats.memory_percent() {
+                    Some(percent) => format!("{} ({:.0}%)", convert_bytes_to_any(current), percent),
+                    None => convert_bytes_to_any(current),
+                }),
+        );
+        ui.next_column();
+        ui.text(match (node.stats.memory_max, node.stats.memory_current) {
+            (Some(max), _) => convert_bytes_to_any(max),
+            (None, Some(_)) => "max".to_string(),
+            (None, None) => "-".to_string(),
+        });
+        ui.next_column();
+        text_or_dash(
+            node.io_read_rate
+                .map(|rate| format!("{}/s", convert_bytes_to_any(rate as u64))),
+        );
+        ui.next_column();
+        text_or_dash(
+            node.io_write_rate
+                .map(|rate| format!("{}/s", convert_bytes_to_any(rate as u64))),
+        );
+        ui.next_column();
+        // Sans contrôleur pids, les processus trouvés dans /proc
+        ui.text(
+            node.stats
+                .pids_current
+                .unwrap_or(node.total_pids() as u64)
+                .to_string(),
+        );
+        ui.next_column();
+        // Non déplié: les descendants ne sont pas affichés
+        if tree {
+            token
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,246 @@
+pub mod command {
+    use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+    use clap::{Args, Parser, Subcommand};
+
+    use crate::{
+        parse_duration, parse_metrics_prefix, parse_nonzero_duration, ConfigOverrides, Metric,
+        MetricSchedule, RecordFormat, RecorderConfig, DEFAULT_AGENT_BIND, DEFAULT_METRICS_BIND,
+        DEFAULT_METRICS_PREFIX,
+    };
+
+    #[derive(Debug, Parser)]
+    #[command(
+        name = "system_monitor",
+        version,
+        about = "System resource and performance monitor"
+    )]
+    pub struct Cli {
+        #[command(subcommand)]
+        pub command: Option<Command>,
+        /// Serve Prometheus metrics on this address while the GUI is running
+        #[arg(long, global = true)]
+        pub metrics_bind: Option<SocketAddr>,
+        /// Prefix of the exported metric names
+        #[arg(
+            long,
+            global = true,
+            default_value = DEFAULT_METRICS_PREFIX,
+            value_parser = parse_metrics_prefix
+        )]
+        pub metrics_prefix: String,
+        /// Replay a recorded session in the GUI instead of reading the live system
+        #[arg(long)]
+        pub replay: Option<PathBuf>,
+        /// Agents to display in the GUI (host:port, repeatable or comma-separated)
+        #[arg(long, value_delimiter = ',', value_name = "HOST:PORT")]
+        pub connect: Vec<String>,
+        /// Session files being recorded (`session` command) to display as hosts, read as they grow
+        #[arg(long, value_delimiter = ',', value_name = "FILE")]
+        pub follow: Vec<PathBuf>,
+        /// Alert rules file (TOML, [[rule]] entries) evaluated by the GUI
+        #[arg(long, value_name = "FILE")]
+        pub rules: Option<PathBuf>,
+        /// Configuration file (default: $XDG_CONFIG_HOME/system_monitor/config.toml)
+        #[arg(long, global = true, value_name = "FILE")]
+        pub config: Option<PathBuf>,
+        /// GUI theme: dark, light, classic, high-contrast or a [themes.<name>] of the config file
+        #[arg(long, value_name = "NAME")]
+        pub theme: Option<String>,
+        /// Graph sampling interval (e.g. 500ms, 2s)
+        #[arg(long, value_parser = parse_duration)]
+        pub interval: Option<Duration>,
+        /// Number of points kept in each graph
+        #[arg(long)]
+        pub history: Option<usize>,
+        /// Process table refresh interval
+        #[arg(long, value_parser = parse_duration)]
+        pub process_refresh: Option<Duration>,
+        /// Panels to hide: system, graphics, memory, events, recorder, alerts, network, fleet, cgroups, services
+        #[arg(long, value_delimiter = ',', value_name = "PANEL")]
+        pub hide: Vec<String>,
+    }
+
+    impl Cli {
+        pub fn config_overrides(&self) -> ConfigOverrides {
+            ConfigOverrides {
+                theme: self.theme.clone(),
+                graph_interval: self.interval,
+                history_length: self.history,
+                process_refresh: self.process_refresh,
+                hidden_panels: self.hide.clone(),
+            }
+        }
+    }
+
+    #[derive(Debug, Subcommand)]
+    pub enum Command {
+        /// Print a one-shot report to stdout without starting the GUI
+        Snapshot(SnapshotArgs),
+        /// Run the monitor in the terminal (tabs, tables and sparklines)
+        Tui,
+        /// Serve Prometheus metrics over HTTP without starting the GUI
+        Exporter(ExporterArgs),
+        /// Record metrics to rotating CSV or NDJSON files without starting the GUI
+        Record(RecordArgs),
+        /// Record full snapshots to a binary session file for later replay (--replay)
+        Session(SessionArgs),
+        /// Print the effective configuration as TOML (a starting point for config.toml)
+        Config,
+        /// Serve snapshots over TCP to remote GUIs (--connect) without starting the GUI
+        Agent(AgentArgs),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Section {
+        System,
+        Cpu,
+        Memory,
+        Network,
+        Processes,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct SnapshotArgs {
+        /// Include system information (hostname, user, OS, CPU model)
+        #[arg(long)]
+        pub system: bool,
+        /// Include CPU usage, temperature and fans
+        #[arg(long)]
+        pub cpu: bool,
+        /// Include RAM, swap and storage
+        #[arg(long)]
+        pub memory: bool,
+        /// Include network interfaces
+        #[arg(long)]
+        pub network: bool,
+        /// Include the process table
+        #[arg(long)]
+        pub processes: bool,
+        /// Number of processes to list, sorted by memory usage (0 lists all)
+        #[arg(long, default_value_t = 10)]
+        pub top: usize,
+        /// Print the snapshot as JSON instead of text
+        #[arg(long)]
+        pub json: bool,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct ExporterArgs {
+        /// Address the HTTP server listens on
+        #[arg(long, default_value = DEFAULT_METRICS_BIND)]
+        pub bind: SocketAddr,
+        /// Number of processes exported, sorted by CPU usage
+        #[arg(long, default_value_t = 10)]
+        pub top: usize,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct RecordArgs {
+        /// Directory receiving the recording files
+        #[arg(long, default_value = "recordings")]
+        pub dir: PathBuf,
+        #[arg(long, value_enum, default_value_t = RecordFormat::Csv)]
+        pub format: RecordFormat,
+        /// Metrics as name[:interval], e.g. cpu_usage:1s,ram_used:5s (default: all, every second)
+        #[arg(long, value_delimiter = ',')]
+        pub metrics: Vec<MetricSchedule>,
+        /// Rotate to a new file beyond this size, in megabytes
+        #[arg(long, default_value_t = 10)]
+        pub max_file_size: u64,
+        /// Number of files kept after rotation
+        #[arg(long, default_value_t = 10)]
+        pub max_files: usize,
+        /// Stop after this duration (e.g. 30s, 10m, 2h); runs until Ctrl-C otherwise
+        #[arg(long, value_parser = parse_duration)]
+        pub duration: Option<Duration>,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct SessionArgs {
+        /// Session file to create
+        #[arg(long, short, default_value = "session.smsession")]
+        pub output: PathBuf,
+        /// Time between two snapshots (e.g. 500ms, 1s, 1m)
+        #[arg(long, default_value = "1s", value_parser = parse_nonzero_duration)]
+        pub interval: Duration,
+        /// Stop after this duration; runs until Ctrl-C otherwise
+        #[arg(long, value_parser = parse_duration)]
+        pub duration: Option<Duration>,
+    }
+
+    #[derive(Debug, Args)]
+    pub struct AgentArgs {
+        /// Address the agent listens on (use 0.0.0.0:9102 to accept remote GUIs)
+        #[arg(long, default_value = DEFAULT_AGENT_BIND)]
+        pub bind: SocketAddr,
+        /// Time between two snapshots (e.g. 500ms, 1s, 5s)
+        #[arg(long, default_value = "1s", value_parser = parse_nonzero_duration)]
+        pub interval: Duration,
+        /// Number of recent snapshots sent to a GUI when it connects
+        #[arg(long, default_value_t = 300)]
+        pub backlog: usize,
+        /// Hostname announced instead of the machine's (e.g. to run several local agents)
+        #[arg(long)]
+        pub hostname: Option<String>,
+        /// Send a simulated, slowly varying load instead of the real measurements
+        #[arg(long)]
+        pub fake: bool,
+    }
+
+    impl RecordArgs {
+        pub fn config(&self) -> RecorderConfig {
+            let metrics = if self.metrics.is_empty() {
+                Metric::ALL
+                    .iter()
+                    .map(|&metric| MetricSchedule {
+                        metric,
+                        interval: Duration::from_secs(1),
+                    })
+                    .collect()
+            } else {
+                self.metrics.clone()
+            };
+            RecorderConfig {
+                directory: self.dir.clone(),
+                format: self.format,
+                metrics,
+                max_file_size: self.max_file_size.max(1) * 1024 * 1024,
+                max_files: self.max_files,
+            }
+        }
+    }
+
+    impl Section {
+        pub const ALL: [Section; 5] = [
+            Section::System,
+            Section::Cpu,
+            Section::Memory,
+            Section::Network,
+            Section::Processes,
+        ];
+    }
+
+    impl SnapshotArgs {
+        // Sans option de section, toutes les sections sont affichées
+        pub fn sections(&self) -> Vec<Section> {
+            let selected = [
+                (self.system, Section::System),
+                (self.cpu, Section::Cpu),
+                (self.memory, Section::Memory),
+                (self.network, Section::Network),
+                (self.processes, Section::Processes),
+            ];
+            let sections: Vec<Section> = selected
+                .iter()
+                .filter(|(enabled, _)| *enabled)
+                .map(|(_, section)| *section)
+                .collect();
+            if sections.is_empty() {
+                Section::ALL.to_vec()
+            } else {
+                sections
+            }
+        }
+    }
+}
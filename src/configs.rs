@@ -0,0 +1,753 @@
+pub mod config {
+    use std::{
+        collections::BTreeMap,
+        env, fs, io,
+        path::{Path, PathBuf},
+        time::{Duration, Instant, SystemTime},
+    };
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{
+        parse_duration, ArchiveSpec, Palette, Theme, ThemeDefinition, DEFAULT_CGROUP_ROOT,
+        DEFAULT_POWERCAP_ROOT, DEFAULT_POWER_SUPPLY_ROOT, DEFAULT_PROC_ROOT, DEFAULT_SYSTEMCTL,
+        DEFAULT_THEME, DEFAULT_UTMP, DEFAULT_WTMP,
+    };
+
+    pub const CONFIG_DIR_NAME: &str = "system_monitor";
+    pub const CONFIG_FILE_NAME: &str = "config.toml";
+    pub const HISTORY_FILE_NAME: &str = "history.sqlite";
+
+    // Durées écrites comme dans la ligne de commande ("500ms", "1s", "5m")
+    mod duration_text {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(
+            duration: &Duration,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&format_duration(*duration))
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Duration, D::Error> {
+            let text = String::deserialize(deserializer)?;
+            parse_duration(&text).map_err(serde::de::Error::custom)
+        }
+    }
+
+    pub fn format_duration(duration: Duration) -> String {
+        let millis = duration.as_millis();
+        if !millis.is_multiple_of(1000) {
+            format!("{}ms", millis)
+        } else if millis.is_multiple_of(86_400_000) && millis > 0 {
+            format!("{}d", millis / 86_400_000)
+        } else if millis.is_multiple_of(3_600_000) && millis > 0 {
+            format!("{}h", millis / 3_600_000)
+        } else if millis.is_multiple_of(60_000) && millis > 0 {
+            format!("{}m", millis / 60_000)
+        } else {
+            format!("{}s", millis / 1000)
+        }
+    }
+
+    // Taille en octets: "2GiB", "512MiB", "1GB" ou un nombre d'octets
+    pub fn parse_size(s: &str) -> Result<u64, String> {
+        let s = s.trim();
+        let (number, unit) = match s.find(|c: char| !c.is_ascii_digit() && c != '.') {
+            Some(i) => s.split_at(i),
+            None => (s, "B"),
+        };
+        let value: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid size '{}'", s))?;
+        let factor: f64 = match unit.trim().to_lowercase().as_str() {
+            "b" | "" => 1.0,
+            "k" | "kib" => 1024.0,
+            "m" | "mib" => 1024.0 * 1024.0,
+            "g" | "gib" => 1024.0 * 1024.0 * 1024.0,
+            "t" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+            "kb" => 1e3,
+            "mb" => 1e6,
+            "gb" => 1e9,
+            "tb" => 1e12,
+            _ => return Err(format!("invalid size unit in '{}'", s)),
+        };
+        Ok((value * factor) as u64)
+    }
+
+    mod size_text {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(size: &u64, serializer: S) -> Result<S::Ok, S::Error> {
+            const MIB: u64 = 1024 * 1024;
+            const GIB: u64 = MIB * 1024;
+            let text = if size.is_multiple_of(GIB) {
+                format!("{}GiB", size / GIB)
+            } else if size.is_multiple_of(MIB) {
+                format!("{}MiB", size / MIB)
+            } else {
+                size.to_string()
+            };
+            serializer.serialize_str(&text)
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+            let text = String::deserialize(deserializer)?;
+            parse_size(&text).map_err(serde::de::Error::custom)
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct SamplingConfig {
+        // Intervalle de mise à jour des graphiques (modifiable ensuite par le slider FPS)
+        #[serde(with = "duration_text")]
+        pub graph_interval: Duration,
+        // Nombre de points conservés par graphique
+        pub history_length: usize,
+        #[serde(with = "duration_text")]
+        pub process_refresh: Duration,
+        #[serde(with = "duration_text")]
+        pub alert_interval: Duration,
+        // Historiques longs des graphiques, "<durée>@<résolution>"
+        pub archives: Vec<ArchiveSpec>,
+    }
+
+    impl Default for SamplingConfig {
+        fn default() -> Self {
+            SamplingConfig {
+                graph_interval: Duration::from_secs(1),
+                history_length: 100,
+                process_refresh: Duration::from_secs(1),
+                alert_interval: Duration::from_secs(1),
+                archives: vec![
+                    ArchiveSpec {
+                        span: Duration::from_secs(3600),
+                        resolution: Duration::from_secs(1),
+                    },
+                    ArchiveSpec {
+                        span: Duration::from_secs(7 * 86400),
+                        resolution: Duration::from_secs(60),
+                    },
+                ],
+            }
+        }
+    }
+
+    // Historique persistant des graphiques (base SQLite)
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct HistoryConfig {
+        pub enabled: bool,
+        // Par défaut $XDG_DATA_HOME/system_monitor/history.sqlite
+        pub path: Option<PathBuf>,
+        // Délai maximal avant l'écriture des échantillons en attente
+        #[serde(with = "duration_text")]
+        pub flush_interval: Duration,
+        // Niveaux de conservation, "<durée>@<résolution>"
+        pub retention: Vec<ArchiveSpec>,
+    }
+
+    impl Default for HistoryConfig {
+        fn default() -> Self {
+            HistoryConfig {
+                enabled: false,
+                path: None,
+                flush_interval: Duration::from_secs(10),
+                retention: vec![
+                    ArchiveSpec {
+                        span: Duration::from_secs(86400),
+                        resolution: Duration::from_secs(10),
+                    },
+                    ArchiveSpec {
+                        span: Duration::from_secs(30 * 86400),
+                        resolution: Duration::from_secs(60),
+                    },
+                    ArchiveSpec {
+                        span: Duration::from_secs(365 * 86400),
+                        resolution: Duration::from_secs(3600),
+                    },
+                ],
+            }
+        }
+    }
+
+    impl HistoryConfig {
+        pub fn path(&self) -> Option<PathBuf> {
+            self.path
+                .clone()
+                .or_else(|| user_data_dir().map(|dir| dir.join(HISTORY_FILE_NAME)))
+        }
+    }
+
+    // Fenêtre "Containers / Slices": racines lisibles ailleurs (ex: arborescence de test)
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct CgroupConfig {
+        pub root: PathBuf,
+        pub proc_root: PathBuf,
+        #[serde(with = "duration_text")]
+        pub refresh: Duration,
+    }
+
+    impl Default for CgroupConfig {
+        fn default() -> Self {
+            CgroupConfig {
+                root: PathBuf::from(DEFAULT_CGROUP_ROOT),
+                proc_root: PathBuf::from(DEFAULT_PROC_ROOT),
+                refresh: Duration::from_secs(2),
+            }
+        }
+    }
+
+    // Alimentations et batteries (onglet Power) et compteurs RAPL (onglet CPU):
+    // racines sysfs remplaçables par une copie
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct PowerConfig {
+        pub supply_root: PathBuf,
+        pub powercap_root: PathBuf,
+    }
+
+    impl Default for PowerConfig {
+        fn default() -> Self {
+            PowerConfig {
+                supply_root: PathBuf::from(DEFAULT_POWER_SUPPLY_ROOT),
+                powercap_root: PathBuf::from(DEFAULT_POWERCAP_ROOT),
+            }
+        }
+    }
+
+    // Section "Sessions" de la fenêtre Système: sessions ouvertes et dernières connexions
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct LoginsConfig {
+        pub utmp: PathBuf,
+        pub wtmp: PathBuf,
+        // Nombre de connexions de l'historique
+        pub history: usize,
+        #[serde(with = "duration_text")]
+        pub refresh: Duration,
+    }
+
+    impl Default for LoginsConfig {
+        fn default() -> Self {
+            LoginsConfig {
+                utmp: PathBuf::from(DEFAULT_UTMP),
+                wtmp: PathBuf::from(DEFAULT_WTMP),
+                history: 20,
+                refresh: Duration::from_secs(5),
+            }
+        }
+    }
+
+    // Fenêtre "Services": état des unités lu avec `systemctl show`
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct ServicesConfig {
+        pub systemctl: PathBuf,
+        #[serde(with = "duration_text")]
+        pub refresh: Duration,
+    }
+
+    impl Default for ServicesConfig {
+        fn default() -> Self {
+            ServicesConfig {
+                systemctl: PathBuf::from(DEFAULT_SYSTEMCTL),
+                refresh: Duration::from_secs(5),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct PanelsConfig {
+        pub system: bool,
+        pub graphics: bool,
+        pub memory: bool,
+        pub events: bool,
+        pub recorder: bool,
+        pub alerts: bool,
+        pub network: bool,
+        // Vue d'ensemble des hôtes, affichée avec --connect ou --follow
+        pub fleet: bool,
+        pub cgroups: bool,
+        pub services: bool,
+    }
+
+    impl Default for PanelsConfig {
+        fn default() -> Self {
+            PanelsConfig {
+                system: true,
+                graphics: true,
+                memory: true,
+                events: true,
+                recorder: true,
+                alerts: true,
+                network: true,
+                fleet: true,
+                cgroups: true,
+                services: true,
+            }
+        }
+    }
+
+    impl PanelsConfig {
+        pub const NAMES: [&'static str; 10] = [
+            "system", "graphics", "memory", "events", "recorder", "alerts", "network", "fleet",
+            "cgroups", "services",
+        ];
+
+        pub fn set(&mut self, name: &str, enabled: bool) -> Result<(), String> {
+            let panel = match name {
+                "system" => &mut self.system,
+                "graphics" => &mut self.graphics,
+                "memory" => &mut self.memory,
+                "events" => &mut self.events,
+                "recorder" => &mut self.recorder,
+                "alerts" => &mut self.alerts,
+                "network" => &mut self.network,
+                "fleet" => &mut self.fleet,
+                "cgroups" => &mut self.cgroups,
+                "services" => &mut self.services,
+                _ => {
+                    return Err(format!(
+                        "unknown panel '{}' (expected one of {})",
+                        name,
+                        Self::NAMES.join(", ")
+                    ))
+                }
+            };
+            *panel = enabled;
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum TemperatureUnit {
+        #[default]
+        Celsius,
+        Fahrenheit,
+    }
+
+    impl TemperatureUnit {
+        pub fn convert(&self, celsius: f32) -> f32 {
+            match self {
+                TemperatureUnit::Celsius => celsius,
+                TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+            }
+        }
+
+        pub fn symbol(&self) -> &'static str {
+            match self {
+                TemperatureUnit::Celsius => "°C",
+                TemperatureUnit::Fahrenheit => "°F",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct UnitsConfig {
+        pub temperature: TemperatureUnit,
+        // Pleine échelle des barres de trafic réseau
+        #[serde(with = "size_text")]
+        pub network_scale: u64,
+    }
+
+    impl Default for UnitsConfig {
+        fn default() -> Self {
+            UnitsConfig {
+                temperature: TemperatureUnit::Celsius,
+                network_scale: 2 * 1024 * 1024 * 1024,
+            }
+        }
+    }
+
+    // Seuils de couleur (en % de l'échelle) entre les niveaux de la palette du thème
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct ThresholdsConfig {
+        pub warning: f64,
+        pub critical: f64,
+        // Seuils des températures, en °C
+        pub temperature_warning: f64,
+        pub temperature_critical: f64,
+    }
+
+    impl Default for ThresholdsConfig {
+        fn default() -> Self {
+            ThresholdsConfig {
+                warning: 50.0,
+                critical: 66.67,
+                temperature_warning: 70.0,
+                temperature_critical: 85.0,
+            }
+        }
+    }
+
+    // Niveaux de la palette, du meilleur au pire
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    pub enum ThresholdLevel {
+        Good,
+        Warning,
+        Critical,
+    }
+
+    impl ThresholdLevel {
+        pub fn color(&self, palette: &Palette) -> [f32; 4] {
+            match self {
+                ThresholdLevel::Good => palette.good.0,
+                ThresholdLevel::Warning => palette.warning.0,
+                ThresholdLevel::Critical => palette.critical.0,
+            }
+        }
+    }
+
+    impl ThresholdsConfig {
+        pub fn level(&self, percent: f64) -> ThresholdLevel {
+            if percent <= self.warning {
+                ThresholdLevel::Good
+            } else if percent <= self.critical {
+                ThresholdLevel::Warning
+            } else {
+                ThresholdLevel::Critical
+            }
+        }
+
+        pub fn temperature_level(&self, celsius: f64) -> ThresholdLevel {
+            if celsius <= self.temperature_warning {
+                ThresholdLevel::Good
+            } else if celsius <= self.temperature_critical {
+                ThresholdLevel::Warning
+            } else {
+                ThresholdLevel::Critical
+            }
+        }
+
+        pub fn color(&self, percent: f64, palette: &Palette) -> [f32; 4] {
+            self.level(percent).color(palette)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct WindowLayout {
+        pub position: [f32; 2],
+        pub size: [f32; 2],
+    }
+
+    impl WindowLayout {
+        const fn new(position: [f32; 2], size: [f32; 2]) -> WindowLayout {
+            WindowLayout { position, size }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct LayoutConfig {
+        pub width: u32,
+        pub height: u32,
+        pub system: WindowLayout,
+        pub graphics: WindowLayout,
+        pub memory: WindowLayout,
+        pub events: WindowLayout,
+        pub recorder: WindowLayout,
+        pub alerts: WindowLayout,
+        pub replay: WindowLayout,
+        pub network: WindowLayout,
+        pub fleet: WindowLayout,
+        pub cgroups: WindowLayout,
+        pub services: WindowLayout,
+    }
+
+    impl Default for LayoutConfig {
+        fn default() -> Self {
+            LayoutConfig {
+                width: 1280,
+                height: 720,
+                system: WindowLayout::new([10.0, 10.0], [600.0, 370.0]),
+                graphics: WindowLayout::new([10.0, 140.0], [600.0, 240.0]),
+                memory: WindowLayout::new([650.0, 10.0], [620.0, 370.0]),
+                events: WindowLayout::new([650.0, 390.0], [620.0, 200.0]),
+                recorder: WindowLayout::new([650.0, 420.0], [620.0, 300.0]),
+                alerts: WindowLayout::new([650.0, 450.0], [620.0, 200.0]),
+                replay: WindowLayout::new([650.0, 600.0], [620.0, 110.0]),
+                network: WindowLayout::new([10.0, 390.0], [1260.0, 310.0]),
+                fleet: WindowLayout::new([10.0, 10.0], [1260.0, 370.0]),
+                cgroups: WindowLayout::new([650.0, 480.0], [620.0, 300.0]),
+                services: WindowLayout::new([650.0, 510.0], [620.0, 300.0]),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct Config {
+        // Nom d'un thème intégré ou de [themes.<nom>]
+        pub theme: String,
+        pub sampling: SamplingConfig,
+        pub panels: PanelsConfig,
+        pub units: UnitsConfig,
+        pub thresholds: ThresholdsConfig,
+        pub layout: LayoutConfig,
+        pub history: HistoryConfig,
+        pub cgroups: CgroupConfig,
+        pub services: ServicesConfig,
+        pub power: PowerConfig,
+        pub logins: LoginsConfig,
+        pub themes: BTreeMap<String, ThemeDefinition>,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Config {
+                theme: DEFAULT_THEME.to_string(),
+                sampling: SamplingConfig::default(),
+                panels: PanelsConfig::default(),
+                units: UnitsConfig::default(),
+                thresholds: ThresholdsConfig::default(),
+                layout: LayoutConfig::default(),
+                history: HistoryConfig::default(),
+                cgroups: CgroupConfig::default(),
+                services: ServicesConfig::default(),
+                power: PowerConfig::default(),
+                logins: LoginsConfig::default(),
+                themes: BTreeMap::new(),
+            }
+        }
+    }
+
+    // Options de la ligne de commande, prioritaires sur le fichier
+    #[derive(Debug, Clone, Default)]
+    pub struct ConfigOverrides {
+        pub theme: Option<String>,
+        pub graph_interval: Option<Duration>,
+        pub history_length: Option<usize>,
+        pub process_refresh: Option<Duration>,
+        pub hidden_panels: Vec<String>,
+    }
+
+    impl Config {
+        pub fn parse(content: &str) -> Result<Config, String> {
+            let config: Config = toml::from_str(content).map_err(|e| e.to_string())?;
+            config.validate()?;
+            Ok(config)
+        }
+
+        pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Config> {
+            let content = fs::read_to_string(&path)?;
+            Config::parse(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+
+        pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+            toml::to_string(self)
+        }
+
+        fn validate(&self) -> Result<(), String> {
+            if self.sampling.history_length < 2 {
+                return Err("sampling.history_length must be at least 2".to_string());
+            }
+            if self.sampling.graph_interval.is_zero() {
+                return Err("sampling.graph_interval must be greater than 0".to_string());
+            }
+            if self.sampling.process_refresh.is_zero() {
+                return Err("sampling.process_refresh must be greater than 0".to_string());
+            }
+            if self.sampling.alert_interval.is_zero() {
+                return Err("sampling.alert_interval must be greater than 0".to_string());
+            }
+            if self.cgroups.refresh.is_zero() {
+                return Err("cgroups.refresh must be greater than 0".to_string());
+            }
+            if self.services.refresh.is_zero() {
+                return Err("services.refresh must be greater than 0".to_string());
+            }
+            if self.logins.refresh.is_zero() {
+                return Err("logins.refresh must be greater than 0".to_string());
+            }
+            if self.history.flush_interval.is_zero() {
+                return Err("history.flush_interval must be greater than 0".to_string());
+            }
+            if self.history.enabled && self.history.retention.is_empty() {
+                return Err("history.retention must not be empty".to_string());
+            }
+            for (index, spec) in self.history.retention.iter().enumerate() {
+                if self.history.retention[..index]
+                    .iter()
+                    .any(|other| other.resolution == spec.resolution)
+                {
+                    return Err(format!(
+                        "history.retention: two levels at the same resolution ({})",
+                        spec
+                    ));
+                }
+            }
+            if self.units.network_scale == 0 {
+                return Err("units.network_scale must be greater than 0".to_string());
+            }
+            for name in self.themes.keys() {
+                Theme::resolve(name, &self.themes)?;
+            }
+            Theme::resolve(&self.theme, &self.themes)?;
+            Ok(())
+        }
+
+        // Les options ne sont appliquées que si la configuration obtenue est valide
+        pub fn apply(&mut self, overrides: &ConfigOverrides) -> Result<(), String> {
+            let mut config = self.clone();
+            if let Some(theme) = &overrides.theme {
+                config.theme = theme.clone();
+            }
+            if let Some(interval) = overrides.graph_interval {
+                config.sampling.graph_interval = interval;
+            }
+            if let Some(length) = overrides.history_length {
+                config.sampling.history_length = length;
+            }
+            if let Some(refresh) = overrides.process_refresh {
+                config.sampling.process_refresh = refresh;
+            }
+            for panel in &overrides.hidden_panels {
+                config.panels.set(panel, false)?;
+            }
+            config.validate()?;
+            *self = config;
+            Ok(())
+        }
+
+        // Valide après validate(): un nom inconnu retombe sur le thème par défaut
+        pub fn resolve_theme(&self, name: &str) -> Theme {
+            Theme::resolve(name, &self.themes).unwrap_or_default()
+        }
+
+        // FPS équivalent à l'intervalle d'échantillonnage des graphiques
+        pub fn graph_fps(&self) -> f32 {
+            1.0 / self.sampling.graph_interval.as_secs_f32()
+        }
+    }
+
+    // Répertoire de configuration de l'utilisateur ($XDG_CONFIG_HOME ou ~/.config)
+    pub fn user_config_dir() -> Option<PathBuf> {
+        let base = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(base.join(CONFIG_DIR_NAME))
+    }
+
+    // Répertoire des données de l'utilisateur ($XDG_DATA_HOME ou ~/.local/share)
+    pub fn user_data_dir() -> Option<PathBuf> {
+        let base = match env::var_os("XDG_DATA_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env::var_os("HOME")?)
+                .join(".local")
+                .join("share"),
+        };
+        Some(base.join(CONFIG_DIR_NAME))
+    }
+
+    // Fichiers candidats par ordre de priorité: utilisateur puis $XDG_CONFIG_DIRS
+    pub fn config_search_paths() -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = user_config_dir()
+            .map(|dir| dir.join(CONFIG_FILE_NAME))
+            .into_iter()
+            .collect();
+        let system_dirs = env::var("XDG_CONFIG_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/etc/xdg".to_string());
+        paths.extend(
+            system_dirs
+                .split(':')
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| Path::new(dir).join(CONFIG_DIR_NAME).join(CONFIG_FILE_NAME)),
+        );
+        paths
+    }
+
+    pub fn find_config_file() -> Option<PathBuf> {
+        config_search_paths()
+            .into_iter()
+            .find(|path| path.is_file())
+    }
+
+    // Recharge la configuration quand la date de modification du fichier change
+    pub struct ConfigWatcher {
+        pub path: Option<PathBuf>,
+        pub overrides: ConfigOverrides,
+        pub error: Option<String>,
+        modified: Option<SystemTime>,
+        last_check: Instant,
+    }
+
+    impl ConfigWatcher {
+        pub const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+        // Sans chemin explicite, le premier fichier trouvé dans les répertoires XDG.
+        // Un fichier invalide est remplacé par la configuration par défaut (erreur dans
+        // `error`), des options invalides sont une erreur
+        pub fn load(
+            path: Option<PathBuf>,
+            overrides: ConfigOverrides,
+        ) -> Result<(ConfigWatcher, Config), String> {
+            let mut watcher = ConfigWatcher {
+                path: path.or_else(find_config_file),
+                overrides,
+                error: None,
+                modified: None,
+                last_check: Instant::now(),
+            };
+            let mut config = watcher.read_file().unwrap_or_else(|e| {
+                watcher.error = Some(e);
+                Config::default()
+            });
+            config.apply(&watcher.overrides)?;
+            Ok((watcher, config))
+        }
+
+        fn modified(&self) -> Option<SystemTime> {
+            let path = self.path.as_ref()?;
+            fs::metadata(path).and_then(|meta| meta.modified()).ok()
+        }
+
+        fn read_file(&mut self) -> Result<Config, String> {
+            self.modified = self.modified();
+            match &self.path {
+                Some(path) => Config::load(path).map_err(|e| format!("{}: {}", path.display(), e)),
+                None => Ok(Config::default()),
+            }
+        }
+
+        fn read(&mut self) -> Result<Config, String> {
+            let mut config = self.read_file()?;
+            config.apply(&self.overrides)?;
+            Ok(config)
+        }
+
+        // Nouvelle configuration si le fichier a changé; en cas d'erreur
+        // l'ancienne configuration reste en place
+        pub fn poll(&mut self) -> Option<Config> {
+            if self.path.is_none() || self.last_check.elapsed() < Self::CHECK_INTERVAL {
+                return None;
+            }
+            self.last_check = Instant::now();
+            if self.modified() == self.modified {
+                return None;
+            }
+            match self.read() {
+                Ok(config) => {
+                    self.error = None;
+                    Some(config)
+                }
+                Err(e) => {
+                    self.error = Some(e);
+                    None
+                }
+            }
+        }
+    }
+}
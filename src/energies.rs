@@ -0,0 +1,236 @@
+pub mod energy {
+    use std::{
+        collections::HashMap,
+        fs, io,
+        path::{Path, PathBuf},
+        time::Instant,
+    };
+
+    use imgui::Ui;
+
+    pub const DEFAULT_POWERCAP_ROOT: &str = "/sys/class/powercap";
+
+    // Domaines RAPL (Intel et AMD exposent tous deux "intel-rapl:*")
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum RaplKind {
+        Package,
+        Core,
+        Dram,
+        Other,
+    }
+
+    impl RaplKind {
+        // Noms du pilote: package-0, core, uncore, dram, psys
+        pub fn from_name(name: &str) -> RaplKind {
+            if name.starts_with("package") {
+                RaplKind::Package
+            } else if name == "core" {
+                RaplKind::Core
+            } else if name == "dram" {
+                RaplKind::Dram
+            } else {
+                RaplKind::Other
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                RaplKind::Package => "Package",
+                RaplKind::Core => "Core",
+                RaplKind::Dram => "DRAM",
+                RaplKind::Other => "Other",
+            }
+        }
+    }
+
+    // Un compteur de /sys/class/powercap/intel-rapl:<socket>[:<sous-domaine>]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct RaplDomain {
+        pub path: PathBuf,
+        pub name: String,
+        pub energy_uj: u64,
+        // Valeur à laquelle energy_uj revient à zéro
+        pub max_energy_range_uj: u64,
+    }
+
+    impl RaplDomain {
+        pub fn read(dir: &Path) -> io::Result<RaplDomain> {
+            let text = |name: &str| -> io::Result<String> {
+                Ok(fs::read_to_string(dir.join(name))?.trim().to_string())
+            };
+            let number = |name: &str| -> io::Result<u64> {
+                text(name)?.parse().map_err(|e| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{}: {}", dir.join(name).display(), e),
+                    )
+                })
+            };
+            Ok(RaplDomain {
+                path: dir.to_path_buf(),
+                name: text("name")?,
+                energy_uj: number("energy_uj")?,
+                max_energy_range_uj: number("max_energy_range_uj")?,
+            })
+        }
+
+        pub fn kind(&self) -> RaplKind {
+            RaplKind::from_name(&self.name)
+        }
+
+        // Socket du domaine, ex: 0 pour intel-rapl:0:1
+        pub fn socket(&self) -> Option<u32> {
+            let name = self.path.file_name()?.to_str()?;
+            name.split(':').nth(1)?.parse().ok()
+        }
+    }
+
+    // Tous les domaines sous `root` (la zone "intel-rapl" sans compteur est ignorée).
+    // energy_uj n'est lisible que par root sur les noyaux récents: l'erreur est remontée
+    pub fn read_rapl_domains(root: &Path) -> io::Result<Vec<RaplDomain>> {
+        let mut domains = Vec::new();
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            let is_rapl = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("intel-rapl:"));
+            if is_rapl {
+                domains.push(RaplDomain::read(&path)?);
+            }
+        }
+        domains.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(domains)
+    }
+
+    // Énergie consommée entre deux lectures, en tenant compte du retour à zéro
+    pub fn energy_delta(previous: u64, current: u64, max_range: u64) -> Option<u64> {
+        if current >= previous {
+            Some(current - previous)
+        } else if max_range >= previous {
+            Some(max_range - previous + current)
+        } else {
+            None
+        }
+    }
+
+    // Puissance et énergie cumulée d'un domaine depuis le démarrage
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DomainPower {
+        pub label: String,
+        pub kind: RaplKind,
+        pub watts: f64,
+        pub joules: f64,
+    }
+
+    // Lectures successives des compteurs RAPL converties en watts
+    pub struct RaplMeter {
+        pub root: PathBuf,
+        pub domains: Vec<DomainPower>,
+        pub error: Option<String>,
+        previous: HashMap<PathBuf, (u64, Instant)>,
+    }
+
+    impl RaplMeter {
+        pub fn new<P: Into<PathBuf>>(root: P) -> RaplMeter {
+            RaplMeter {
+                root: root.into(),
+                domains: Vec::new(),
+                error: None,
+                previous: HashMap::new(),
+            }
+        }
+
+        // Les puissances ne sont connues qu'à partir de la deuxième lecture
+        pub fn sample(&mut self) {
+            let domains = match read_rapl_domains(&self.root) {
+                Ok(domains) if domains.is_empty() => {
+                    self.error = Some(format!("No RAPL domain in {}", self.root.display()));
+                    return;
+                }
+                Ok(domains) => domains,
+                Err(e) => {
+                    self.error = Some(format!("{}: {}", self.root.display(), e));
+                    return;
+                }
+            };
+            self.error = None;
+            let now = Instant::now();
+            for domain in domains {
+                let label = match domain.socket() {
+                    Some(socket) if domain.kind() != RaplKind::Package => {
+                        format!("{} ({})", domain.name, socket)
+                    }
+                    _ => domain.name.clone(),
+                };
+                let previous = self
+                    .previous
+                    .insert(domain.path.clone(), (domain.energy_uj, now));
+                let Some((energy, time)) = previous else {
+                    continue;
+                };
+                let elapsed = now.duration_since(time).as_secs_f64();
+                let Some(delta) =
+                    energy_delta(energy, domain.energy_uj, domain.max_energy_range_uj)
+                else {
+                    continue;
+                };
+                if elapsed <= 0.0 {
+                    continue;
+                }
+                let joules = delta as f64 / 1e6;
+                match self.domains.iter_mut().find(|power| power.label == label) {
+                    Some(power) => {
+                        power.watts = joules / elapsed;
+                        power.joules += joules;
+                    }
+                    None => self.domains.push(DomainPower {
+                        label,
+                        kind: domain.kind(),
+                        watts: joules / elapsed,
+                        joules,
+                    }),
+                }
+            }
+        }
+
+        // Somme des sockets pour un type de domaine, si la machine l'expose
+        pub fn watts(&self, kind: RaplKind) -> Option<f64> {
+            self.domains
+                .iter()
+                .filter(|power| power.kind == kind)
+                .map(|power| power.watts)
+                .reduce(|a, b| a + b)
+        }
+
+        pub fn joules(&self, kind: RaplKind) -> Option<f64> {
+            self.domains
+                .iter()
+                .filter(|power| power.kind == kind)
+                .map(|power| power.joules)
+                .reduce(|a, b| a + b)
+        }
+    }
+
+    // Puissance de chaque domaine et énergie cumulée (onglet CPU)
+    pub fn draw_rapl(ui: &Ui, meter: &RaplMeter) {
+        if let Some(error) = &meter.error {
+            ui.text_disabled(format!("CPU power unavailable: {}", error));
+            return;
+        }
+        let line: Vec<String> = meter
+            .domains
+            .iter()
+            .map(|power| format!("{}: {:.1} W", power.label, power.watts))
+            .collect();
+        if !line.is_empty() {
+            ui.text(line.join("   "));
+        }
+        if let Some(joules) = meter.joules(RaplKind::Package) {
+            ui.text(format!(
+                "Package energy since start: {:.2} Wh",
+                joules / 3600.0
+            ));
+        }
+    }
+}
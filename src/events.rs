@@ -0,0 +1,275 @@
+pub mod event {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader, ErrorKind},
+        path::PathBuf,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        thread,
+        time::{Duration, SystemTime},
+    };
+
+    use chrono::{DateTime, Local};
+    use imgui::Ui;
+    use regex::Regex;
+    use sysinfo::System;
+
+    pub const DEFAULT_KMSG_PATH: &str = "/dev/kmsg";
+    // Événements conservés pour la fenêtre "Events": les plus anciens sont retirés
+    const MAX_EVENTS: usize = 1000;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum EventKind {
+        OomKill,
+        Segfault,
+        MachineCheck,
+        IoError,
+    }
+
+    impl EventKind {
+        pub fn label(&self) -> &'static str {
+            match self {
+                EventKind::OomKill => "OOM Kill",
+                EventKind::Segfault => "Segfault",
+                EventKind::MachineCheck => "MCE",
+                EventKind::IoError => "I/O Error",
+            }
+        }
+
+        pub fn color(&self) -> [f32; 4] {
+            match self {
+                EventKind::OomKill => [1.0, 0.3, 0.3, 1.0],
+                EventKind::Segfault => [1.0, 0.6, 0.2, 1.0],
+                EventKind::MachineCheck => [1.0, 0.2, 1.0, 1.0],
+                EventKind::IoError => [1.0, 1.0, 0.3, 1.0],
+            }
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct KernelEvent {
+        pub kind: EventKind,
+        pub timestamp: SystemTime,
+        pub process: Option<String>,
+        pub pid: Option<u32>,
+        pub message: String,
+    }
+
+    // Expressions régulières utilisées pour classer les messages du noyau
+    pub struct EventParser {
+        oom: Regex,
+        segfault: Regex,
+        mce: Regex,
+        io_error: Regex,
+        boot_time: SystemTime,
+    }
+
+    impl EventParser {
+        pub fn new() -> EventParser {
+            EventParser {
+                oom: Regex::new(r"(?:Out of memory|oom-kill|Memory cgroup out of memory).*?Killed process (\d+) \(([^)]+)\)|Killed process (\d+) \(([^)]+)\)").unwrap(),
+                segfault: Regex::new(r"([^\s\[]+)\[(\d+)\]: segfault at").unwrap(),
+                mce: Regex::new(r"(?i)(mce:|machine check|\[Hardware Error\])").unwrap(),
+                io_error: Regex::new(r"(?i)I/O error").unwrap(),
+                boot_time: SystemTime::UNIX_EPOCH + Duration::from_secs(System::boot_time()),
+            }
+        }
+
+        // Analyse une ligne au format /dev/kmsg ("prio,seq,usec,flags;message"),
+        // au format dmesg ("[  12.345678] message") ou un message brut.
+        pub fn parse_line(&self, line: &str) -> Option<KernelEvent> {
+            let (uptime, message) = split_timestamp(line);
+            let timestamp = match uptime {
+                Some(uptime) => self.boot_time + uptime,
+                None => SystemTime::now(),
+            };
+
+            let (kind, process, pid) = if let Some(caps) = self.oom.captures(message) {
+                let pid = caps.get(1).or(caps.get(3)).and_then(|m| m.as_str().parse().ok());
+                let name = caps.get(2).or(caps.get(4)).map(|m| m.as_str().to_string());
+                (EventKind::OomKill, name, pid)
+            } else if let Some(caps) = self.segfault.captures(message) {
+                (
+                    EventKind::Segfault,
+                    Some(caps[1].to_string()),
+                    caps[2].parse().ok(),
+                )
+            } else if self.mce.is_match(message) {
+                (EventKind::MachineCheck, None, None)
+            } else if self.io_error.is_match(message) {
+                (EventKind::IoError, None, None)
+            } else {
+                return None;
+            };
+
+            Some(KernelEvent {
+                kind,
+                timestamp,
+                process,
+                pid,
+                message: message.trim().to_string(),
+            })
+        }
+    }
+
+    impl Default for EventParser {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    fn split_timestamp(line: &str) -> (Option<Duration>, &str) {
+        // Format /dev/kmsg
+        if let Some((header, message)) = line.split_once(';') {
+            let fields: Vec<&str> = header.split(',').collect();
+            if fields.len() >= 3 && fields[0].parse::<u32>().is_ok() {
+                if let Ok(usec) = fields[2].parse::<u64>() {
+                    return (Some(Duration::from_micros(usec)), message);
+                }
+            }
+        }
+        // Format dmesg
+        if let Some(rest) = line.trim_start().strip_prefix('[') {
+            if let Some((secs, message)) = rest.split_once(']') {
+                if let Ok(secs) = secs.trim().parse::<f64>() {
+                    // "[nan]", "[-1]"...: message gardé, horodaté à la lecture
+                    return (Duration::try_from_secs_f64(secs).ok(), message);
+                }
+            }
+        }
+        (None, line)
+    }
+
+    // Lit /dev/kmsg (ou un fichier quelconque) en continu dans un thread dédié
+    pub struct EventWatcher {
+        pub path: PathBuf,
+        pub events: Arc<Mutex<Vec<KernelEvent>>>,
+        pub error: Arc<Mutex<Option<String>>>,
+        // Nombre d'événements lus depuis le lancement, "Clear" et la limite n'y touchent pas
+        pub received: Arc<AtomicUsize>,
+    }
+
+    impl EventWatcher {
+        pub fn spawn<P: Into<PathBuf>>(path: P) -> EventWatcher {
+            let watcher = EventWatcher {
+                path: path.into(),
+                events: Arc::new(Mutex::new(Vec::new())),
+                error: Arc::new(Mutex::new(None)),
+                received: Arc::new(AtomicUsize::new(0)),
+            };
+            let path = watcher.path.clone();
+            let events = watcher.events.clone();
+            let error = watcher.error.clone();
+            let received = watcher.received.clone();
+            thread::spawn(move || {
+                let file = match File::open(&path) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        *error.lock().unwrap() = Some(format!("{}: {}", path.display(), e));
+                        return;
+                    }
+                };
+                let parser = EventParser::new();
+                let mut reader = BufReader::new(file);
+                let mut line = String::new();
+                loop {
+                    line.clear();
+                    match reader.read_line(&mut line) {
+                        // Fin d'un fichier ordinaire: on attend de nouvelles lignes (comme tail -f)
+                        Ok(0) => thread::sleep(Duration::from_millis(500)),
+                        Ok(_) => {
+                            if let Some(event) = parser.parse_line(line.trim_end()) {
+                                let mut events = events.lock().unwrap();
+                                events.push(event);
+                                let excess = events.len().saturating_sub(MAX_EVENTS);
+                                events.drain(..excess);
+                                received.fetch_add(1, Ordering::Relaxed);
+                            }
+                        }
+                        // /dev/kmsg renvoie EPIPE quand des messages ont été écrasés
+                        Err(e) if e.kind() == ErrorKind::BrokenPipe => continue,
+                        Err(e) => {
+                            *error.lock().unwrap() = Some(format!("{}: {}", path.display(), e));
+                            return;
+                        }
+                    }
+                }
+            });
+            watcher
+        }
+
+        // Chemin configurable via SYSTEM_MONITOR_KMSG (utile pour les tests)
+        pub fn from_env() -> EventWatcher {
+            let path = std::env::var("SYSTEM_MONITOR_KMSG")
+                .unwrap_or_else(|_| DEFAULT_KMSG_PATH.to_string());
+            Self::spawn(path)
+        }
+
+        pub fn len(&self) -> usize {
+            self.events.lock().unwrap().len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        pub fn received(&self) -> usize {
+            self.received.load(Ordering::Relaxed)
+        }
+
+        // Horodatage des `count` derniers événements encore conservés
+        pub fn latest_timestamps(&self, count: usize) -> Vec<SystemTime> {
+            let events = self.events.lock().unwrap();
+            let skip = events.len().saturating_sub(count);
+            events.iter().skip(skip).map(|event| event.timestamp).collect()
+        }
+    }
+
+    pub fn format_timestamp(timestamp: SystemTime) -> String {
+        let datetime: DateTime<Local> = timestamp.into();
+        datetime.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+
+    pub fn draw_events_table(ui: &Ui, watcher: &EventWatcher) {
+        if let Some(error) = watcher.error.lock().unwrap().as_ref() {
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("Unable to read kernel log: {}", error));
+        }
+        let mut events = watcher.events.lock().unwrap();
+        ui.text(format!("Source: {}", watcher.path.display()));
+        ui.text(format!("Total Events: {}", events.len()));
+        ui.same_line();
+        if ui.button("Clear") {
+            events.clear();
+        }
+        ui.separator();
+
+        ui.columns(4, "EventColumns", true);
+        ui.text("Time");
+        ui.next_column();
+        ui.text("Type");
+        ui.next_column();
+        ui.text("Process");
+        ui.next_column();
+        ui.text("Message");
+        ui.next_column();
+        ui.separator();
+        // Les événements les plus récents en premier
+        for event in events.iter().rev() {
+            ui.text(format_timestamp(event.timestamp));
+            ui.next_column();
+            ui.text_colored(event.kind.color(), event.kind.label());
+            ui.next_column();
+            match (&event.process, event.pid) {
+                (Some(name), Some(pid)) => ui.text(format!("{} ({})", name, pid)),
+                (Some(name), None) => ui.text(name),
+                _ => ui.text("-"),
+            }
+            ui.next_column();
+            ui.text(&event.message);
+            ui.next_column();
+        }
+        ui.columns(1, "", false);
+    }
+}
@@ -0,0 +1,361 @@
+pub mod prometheus {
+    use std::{
+        collections::HashMap,
+        fmt::Write as _,
+        io,
+        net::SocketAddr,
+        sync::{Arc, Mutex},
+    };
+
+    use sysinfo::{Components, Disks, NetworkData, Networks, System};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use crate::{Cpu, MemInfo};
+
+    pub const DEFAULT_METRICS_BIND: &str = "127.0.0.1:9101";
+    pub const DEFAULT_METRICS_PREFIX: &str = "system_monitor";
+
+    #[derive(Debug, Clone)]
+    pub struct ExporterConfig {
+        pub bind: SocketAddr,
+        pub prefix: String,
+        // Nombre de processus exportés (triés par utilisation CPU)
+        pub top_processes: usize,
+    }
+
+    // Écrit des métriques au format texte d'exposition Prometheus
+    pub struct MetricsWriter {
+        prefix: String,
+        out: String,
+    }
+
+    impl MetricsWriter {
+        pub fn new(prefix: &str) -> MetricsWriter {
+            MetricsWriter {
+                prefix: prefix.trim_end_matches('_').to_string(),
+                out: String::new(),
+            }
+        }
+
+        pub fn family(&mut self, name: &str, kind: &str, help: &str) {
+            let _ = writeln!(self.out, "# HELP {}_{} {}", self.prefix, name, help);
+            let _ = writeln!(self.out, "# TYPE {}_{} {}", self.prefix, name, kind);
+        }
+
+        pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+            let _ = write!(self.out, "{}_{}", self.prefix, name);
+            if !labels.is_empty() {
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+                    .collect();
+                let _ = write!(self.out, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(self.out, " {}", value);
+        }
+
+        pub fn finish(self) -> String {
+            self.out
+        }
+    }
+
+    // Préfixe des noms de métriques: [a-zA-Z_:][a-zA-Z0-9_:]*, sinon les scrapers
+    // rejettent toute l'exposition
+    pub fn parse_metrics_prefix(s: &str) -> Result<String, String> {
+        let mut chars = s.chars();
+        let valid = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == ':')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':');
+        if !valid {
+            return Err(format!(
+                "invalid metric prefix '{}' (expected [a-zA-Z_:][a-zA-Z0-9_:]*)",
+                s
+            ));
+        }
+        Ok(s.to_string())
+    }
+
+    // Étiquettes rendues uniques dans l'ordre: "Core 0", "Core 0 #2", ... pour ne pas
+    // exporter deux séries identiques (ex: deux puces avec le même nom de capteur)
+    pub fn unique_labels<'a>(labels: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        labels
+            .into_iter()
+            .map(|label| {
+                let count = seen.entry(label).or_insert(0);
+                *count += 1;
+                if *count == 1 {
+                    label.to_string()
+                } else {
+                    format!("{} #{}", label, count)
+                }
+            })
+            .collect()
+    }
+
+    fn escape_label(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n")
+    }
+
+    // (nom, aide, lecture du compteur)
+    type NetworkCounter = (&'static str, &'static str, fn(&NetworkData) -> u64);
+
+    // Garde les structures sysinfo entre deux collectes pour calculer l'utilisation CPU
+    pub struct MetricsCollector {
+        system: System,
+        disks: Disks,
+        components: Components,
+        networks: Networks,
+    }
+
+    impl MetricsCollector {
+        pub fn new() -> MetricsCollector {
+            let mut system = System::new();
+            system.refresh_cpu_usage();
+            MetricsCollector {
+                system,
+                disks: Disks::new_with_refreshed_list(),
+                components: Components::new_with_refreshed_list(),
+                networks: Networks::new_with_refreshed_list(),
+            }
+        }
+
+        pub fn render(&mut self, prefix: &str, top_processes: usize) -> String {
+            self.system.refresh_cpu_usage();
+            self.system.refresh_memory();
+            self.system.refresh_processes();
+            self.disks.refresh_list();
+            self.components.refresh();
+            self.networks.refresh_list();
+
+            let mut w = MetricsWriter::new(prefix);
+
+            w.family("cpu_usage_percent", "gauge", "Global CPU usage in percent.");
+            w.sample(
+                "cpu_usage_percent",
+                &[],
+                self.system.global_cpu_info().cpu_usage() as f64,
+            );
+            w.family(
+                "cpu_core_usage_percent",
+                "gauge",
+                "Per-core CPU usage in percent.",
+            );
+            for (i, cpu) in self.system.cpus().iter().enumerate() {
+                w.sample(
+                    "cpu_core_usage_percent",
+                    &[("core", &i.to_string())],
+                    cpu.cpu_usage() as f64,
+                );
+            }
+
+            let meminfo = MemInfo::read().unwrap_or_default();
+            w.family("memory_total_bytes", "gauge", "Total RAM in bytes.");
+            w.sample("memory_total_bytes", &[], self.system.total_memory() as f64);
+            w.family("memory_used_bytes", "gauge", "Used RAM in bytes.");
+            w.sample("memory_used_bytes", &[], self.system.used_memory() as f64);
+            w.family("memory_available_bytes", "gauge", "Available RAM in bytes.");
+            w.sample(
+                "memory_available_bytes",
+                &[],
+                self.system.available_memory() as f64,
+            );
+            w.family(
+                "memory_cache_bytes",
+                "gauge",
+                "Page cache and reclaimable slab in bytes.",
+            );
+            w.sample("memory_cache_bytes", &[], meminfo.cache() as f64);
+            w.family("swap_total_bytes", "gauge", "Total swap in bytes.");
+            w.sample("swap_total_bytes", &[], self.system.total_swap() as f64);
+            w.family("swap_used_bytes", "gauge", "Used swap in bytes.");
+            w.sample("swap_used_bytes", &[], self.system.used_swap() as f64);
+
+            w.family("disk_total_bytes", "gauge", "Disk size in bytes.");
+            for disk in &self.disks {
+                let mount = disk.mount_point().to_string_lossy();
+                let device = disk.name().to_string_lossy();
+                w.sample(
+                    "disk_total_bytes",
+                    &[("device", &device), ("mountpoint", &mount)],
+                    disk.total_space() as f64,
+                );
+            }
+            w.family(
+                "disk_available_bytes",
+                "gauge",
+                "Available disk space in bytes.",
+            );
+            for disk in &self.disks {
+                let mount = disk.mount_point().to_string_lossy();
+                let device = disk.name().to_string_lossy();
+                w.sample(
+                    "disk_available_bytes",
+                    &[("device", &device), ("mountpoint", &mount)],
+                    disk.available_space() as f64,
+                );
+            }
+
+            w.family(
+                "temperature_celsius",
+                "gauge",
+                "Component temperature in degrees Celsius.",
+            );
+            let sensors = unique_labels(self.components.iter().map(|component| component.label()));
+            for (component, sensor) in self.components.iter().zip(&sensors) {
+                w.sample(
+                    "temperature_celsius",
+                    &[("sensor", sensor)],
+                    component.temperature() as f64,
+                );
+            }
+
+            w.family("fan_speed_rpm", "gauge", "Fan speed in RPM.");
+            for (i, fan) in Cpu::get_all_fan_info()
+                .unwrap_or_default()
+                .iter()
+                .enumerate()
+            {
+                w.sample(
+                    "fan_speed_rpm",
+                    &[("fan", &(i + 1).to_string())],
+                    fan.rpm.unwrap_or(0) as f64,
+                );
+            }
+
+            let counters: [NetworkCounter; 6] = [
+                ("network_receive_bytes_total", "Bytes received.", |d| {
+                    d.total_received()
+                }),
+                ("network_transmit_bytes_total", "Bytes transmitted.", |d| {
+                    d.total_transmitted()
+                }),
+                ("network_receive_packets_total", "Packets received.", |d| {
+                    d.total_packets_received()
+                }),
+                (
+                    "network_transmit_packets_total",
+                    "Packets transmitted.",
+                    |d| d.total_packets_transmitted(),
+                ),
+                ("network_receive_errors_total", "Receive errors.", |d| {
+                    d.total_errors_on_received()
+                }),
+                ("network_transmit_errors_total", "Transmit errors.", |d| {
+                    d.total_errors_on_transmitted()
+                }),
+            ];
+            for (name, help, value) in counters {
+                w.family(name, "counter", help);
+                for (interface, data) in &self.networks {
+                    w.sample(name, &[("interface", interface)], value(data) as f64);
+                }
+            }
+
+            let mut processes: Vec<_> = self.system.processes().values().collect();
+            processes.sort_by(|a, b| b.cpu_usage().total_cmp(&a.cpu_usage()));
+            processes.truncate(top_processes);
+            w.family(
+                "process_cpu_usage_percent",
+                "gauge",
+                "CPU usage of the top processes.",
+            );
+            for process in &processes {
+                let pid = process.pid().to_string();
+                w.sample(
+                    "process_cpu_usage_percent",
+                    &[("pid", &pid), ("name", process.name())],
+                    process.cpu_usage() as f64,
+                );
+            }
+            w.family(
+                "process_memory_bytes",
+                "gauge",
+                "Resident memory of the top processes.",
+            );
+            for process in &processes {
+                let pid = process.pid().to_string();
+                w.sample(
+                    "process_memory_bytes",
+                    &[("pid", &pid), ("name", process.name())],
+                    process.memory() as f64,
+                );
+            }
+
+            w.finish()
+        }
+    }
+
+    impl Default for MetricsCollector {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Serveur HTTP minimal: GET /metrics sur le runtime tokio existant
+    pub async fn serve_metrics(config: ExporterConfig) -> io::Result<()> {
+        let listener = TcpListener::bind(config.bind).await?;
+        let collector = Arc::new(Mutex::new(MetricsCollector::new()));
+        let config = Arc::new(config);
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let collector = collector.clone();
+            let config = config.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, collector, config).await {
+                    eprintln!("Metrics exporter: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(
+        mut stream: TcpStream,
+        collector: Arc<Mutex<MetricsCollector>>,
+        config: Arc<ExporterConfig>,
+    ) -> io::Result<()> {
+        let mut buffer = [0u8; 4096];
+        let read = stream.read(&mut buffer).await?;
+        let request = String::from_utf8_lossy(&buffer[..read]);
+        let mut parts = request.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+        let (status, content_type, body) = match (method, path) {
+            ("GET", "/metrics") => {
+                // La collecte lit /proc et lance `sensors`: hors du thread asynchrone
+                let body = tokio::task::spawn_blocking(move || {
+                    collector
+                        .lock()
+                        .unwrap()
+                        .render(&config.prefix, config.top_processes)
+                })
+                .await
+                .map_err(io::Error::other)?;
+                ("200 OK", "text/plain; version=0.0.4; charset=utf-8", body)
+            }
+            ("GET", "/") => (
+                "200 OK",
+                "text/html; charset=utf-8",
+                "<html><body><a href=\"/metrics\">Metrics</a></body></html>\n".to_string(),
+            ),
+            _ => ("404 Not Found", "text/plain", "Not Found\n".to_string()),
+        };
+
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            content_type,
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
@@ -0,0 +1,413 @@
+pub mod fleet {
+    use chrono::{DateTime, Local, Utc};
+    use imgui::{StyleColor, Ui};
+    use std::cmp::Ordering;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        Arc, Mutex,
+    };
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use crate::{
+        Palette, RemoteStatus, Snapshot, SnapshotCollector, TemperatureUnit, ThresholdLevel,
+        ThresholdsConfig,
+    };
+
+    const TILE_SIZE: [f32; 2] = [240.0, 128.0];
+
+    // État d'un hôte résumé dans une tuile de la vue d'ensemble
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct HostSummary {
+        // Nom d'hôte du snapshot, sinon l'origine
+        pub name: String,
+        // Origine: "Local", adresse de l'agent ou session suivie
+        pub source: String,
+        pub status: RemoteStatus,
+        pub cpu: Option<f32>,
+        pub memory: Option<f32>,
+        // Disque le plus rempli, en %
+        pub disk: Option<f32>,
+        // Capteur le plus chaud, en °C
+        pub temperature: Option<f32>,
+        pub alerts: usize,
+        pub updated: Option<DateTime<Utc>>,
+    }
+
+    impl HostSummary {
+        pub fn from_snapshot(
+            source: &str,
+            snapshot: Option<&Snapshot>,
+            status: RemoteStatus,
+            alerts: usize,
+        ) -> HostSummary {
+            let name = snapshot
+                .and_then(|snapshot| snapshot.computer.as_ref())
+                .map(|computer| computer.hostname.clone())
+                .filter(|hostname| !hostname.is_empty())
+                .unwrap_or_else(|| source.to_string());
+            let cpu = snapshot.and_then(|snapshot| snapshot.cpu.as_ref());
+            HostSummary {
+                name,
+                source: source.to_string(),
+                status,
+                cpu: cpu.map(|cpu| cpu.usage),
+                memory: snapshot
+                    .and_then(|snapshot| snapshot.meminfo.as_ref())
+                    .filter(|meminfo| meminfo.mem_total > 0)
+                    .map(|meminfo| meminfo.used_percent()),
+                disk: snapshot
+                    .and_then(|snapshot| snapshot.disks.as_ref())
+                    .and_then(|disks| {
+                        disks
+                            .iter()
+                            .filter(|disk| disk.total_space > 0)
+                            .map(|disk| disk.used_percent())
+                            .max_by(f32::total_cmp)
+                    }),
+                temperature: cpu.and_then(|cpu| {
+                    cpu.sensors
+                        .iter()
+                        .map(|sensor| sensor.temperature)
+                        .chain(std::iter::once(cpu.temperature))
+                        .filter(|temperature| *temperature > 0.0)
+                        .max_by(f32::total_cmp)
+                }),
+                alerts,
+                updated: snapshot.map(|snapshot| snapshot.timestamp),
+            }
+        }
+
+        pub fn is_connected(&self) -> bool {
+            self.status == RemoteStatus::Connected
+        }
+
+        // Pire niveau des mesures; une alerte active rend l'hôte critique
+        pub fn level(&self, thresholds: &ThresholdsConfig) -> ThresholdLevel {
+            let percents = [self.cpu, self.memory, self.disk]
+                .into_iter()
+                .flatten()
+                .map(|percent| thresholds.level(percent as f64));
+            let temperature = self
+                .temperature
+                .map(|celsius| thresholds.temperature_level(celsius as f64));
+            let alerts = (self.alerts > 0).then_some(ThresholdLevel::Critical);
+            percents
+                .chain(temperature)
+                .chain(alerts)
+                .max()
+                .unwrap_or(ThresholdLevel::Good)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum FleetSort {
+        #[default]
+        Name,
+        Cpu,
+        Memory,
+        Disk,
+        Temperature,
+        Alerts,
+    }
+
+    impl FleetSort {
+        pub const ALL: [FleetSort; 6] = [
+            FleetSort::Name,
+            FleetSort::Cpu,
+            FleetSort::Memory,
+            FleetSort::Disk,
+            FleetSort::Temperature,
+            FleetSort::Alerts,
+        ];
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                FleetSort::Name => "Name",
+                FleetSort::Cpu => "CPU",
+                FleetSort::Memory => "Memory",
+                FleetSort::Disk => "Disk",
+                FleetSort::Temperature => "Temperature",
+                FleetSort::Alerts => "Alerts",
+            }
+        }
+
+        fn compare(&self, a: &HostSummary, b: &HostSummary) -> Ordering {
+            let value = |host: &HostSummary| match self {
+                FleetSort::Name | FleetSort::Alerts => None,
+                FleetSort::Cpu => host.cpu,
+                FleetSort::Memory => host.memory,
+                FleetSort::Disk => host.disk,
+                FleetSort::Temperature => host.temperature,
+            };
+            match self {
+                FleetSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                FleetSort::Alerts => a.alerts.cmp(&b.alerts),
+                _ => match (value(a), value(b)) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    (a, b) => a.is_some().cmp(&b.is_some()),
+                },
+            }
+        }
+    }
+
+    // Tri choisi dans la vue d'ensemble
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct FleetView {
+        pub sort: FleetSort,
+        pub descending: bool,
+    }
+
+    impl FleetView {
+        pub fn new() -> FleetView {
+            Self::default()
+        }
+
+        // Indices des hôtes dans l'ordre d'affichage, à nom égal dans l'ordre d'origine
+        pub fn order(&self, hosts: &[HostSummary]) -> Vec<usize> {
+            let mut order: Vec<usize> = (0..hosts.len()).collect();
+            order.sort_by(|&a, &b| {
+                let ordering = self.sort.compare(&hosts[a], &hosts[b]);
+                if self.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+            order
+        }
+    }
+
+    // Snapshots de la machine locale pour sa tuile, collectés hors du thread de l'interface
+    pub struct LocalSampler {
+        pub latest: Arc<Mutex<Option<Snapshot>>>,
+        running: Arc<AtomicBool>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl LocalSampler {
+        pub fn start(interval: Duration) -> LocalSampler {
+            let latest = Arc::new(Mutex::new(None));
+            let running = Arc::new(AtomicBool::new(true));
+            let handle = {
+                let latest = latest.clone();
+                let running = running.clone();
+                thread::spawn(move || {
+                    let mut collector = SnapshotCollector::new();
+                    thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+                    while running.load(AtomicOrdering::Relaxed) {
+                        let mut snapshot = collector.collect();
+                        snapshot.processes = None;
+                        *latest.lock().unwrap() = Some(snapshot);
+                        let mut waited = Duration::ZERO;
+                        while running.load(AtomicOrdering::Relaxed) && waited < interval {
+                            thread::sleep(Duration::from_millis(100));
+                            waited += Duration::from_millis(100);
+                        }
+                    }
+                })
+            };
+            LocalSampler {
+                latest,
+                running,
+                handle: Some(handle),
+            }
+        }
+    }
+
+    impl Drop for LocalSampler {
+        fn drop(&mut self) {
+            self.running.store(false, AtomicOrdering::Relaxed);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    // Une tuile par hôte, colorée selon son pire niveau; renvoie l'indice de l'hôte cliqué
+    pub fn draw_fleet(
+        ui: &Ui,
+        hosts: &[HostSummary],
+        current: usize,
+        view: &mut FleetView,
+        thresholds: &ThresholdsConfig,
+        palette: &Palette,
+        unit: TemperatureUnit,
+    ) -> Option<usize> {
+        let levels: Vec<ThresholdLevel> = hosts.iter().map(|host| host.level(thresholds)).collect();
+        let count = |level: ThresholdLevel| {
+            hosts
+                .iter()
+                .zip(&levels)
+                .filter(|(host, host_level)| host.is_connected() && **host_level == level)
+                .count()
+        };
+        ui.text(format!(
+            "Hosts: {}   Warning: {}   Critical: {}   Disconnected: {}",
+            hosts.len(),
+            count(ThresholdLevel::Warning),
+            count(ThresholdLevel::Critical),
+            hosts.iter().filter(|host| !host.is_connected()).count()
+        ));
+        let labels: Vec<&str> = FleetSort::ALL.iter().map(FleetSort::label).collect();
+        let mut sort_index = FleetSort::ALL
+            .iter()
+            .position(|sort| *sort == view.sort)
+            .unwrap_or(0);
+        ui.set_next_item_width(150.0);
+        if ui.combo_simple_string("Sort by", &mut sort_index, &labels) {
+            view.sort = FleetSort::ALL[sort_index];
+        }
+        ui.same_line();
+        ui.checkbox("Descending", &mut view.descending);
+        ui.separator();
+
+        let style = ui.clone_style();
+        let text_color = style.colors[StyleColor::Text as usize];
+        let disabled = style.colors[StyleColor::TextDisabled as usize];
+        let line_height = ui.text_line_height();
+        let padding = style.frame_padding;
+        let spacing = style.item_spacing[0];
+        let per_row = ((ui.content_region_avail()[0] + spacing) / (TILE_SIZE[0] + spacing))
+            .floor()
+            .max(1.0) as usize;
+        let mut clicked = None;
+        for (position, &index) in view.order(hosts).iter().enumerate() {
+            let host = &hosts[index];
+            if position % per_row != 0 {
+                ui.same_line();
+            }
+            if ui.invisible_button(format!("##fleet{}", index), TILE_SIZE) {
+                clicked = Some(index);
+            }
+            let hovered = ui.is_item_hovered();
+            let min = ui.item_rect_min();
+            let max = ui.item_rect_max();
+            let connected = host.is_connected();
+            let (text, border) = if connected {
+                (text_color, levels[index].color(palette))
+            } else {
+                (disabled, disabled)
+            };
+            let draw_list = ui.get_window_draw_list();
+            let background = if hovered {
+                StyleColor::FrameBgHovered
+            } else {
+                StyleColor::FrameBg
+            };
+            draw_list
+                .add_rect(min, max, style.colors[background as usize])
+                .filled(true)
+                .rounding(style.frame_rounding)
+                .build();
+            draw_list
+                .add_rect(min, max, border)
+                .rounding(style.frame_rounding)
+                .thickness(if index == current { 4.0 } else { 2.0 })
+                .build();
+
+            let left = min[0] + padding[0] * 2.0;
+            let right = max[0] - padding[0] * 2.0;
+            let mut y = min[1] + padding[1] * 2.0;
+            draw_list.add_text([left, y], text, &host.name);
+            y += line_height * 1.3;
+
+            // Une ligne par mesure: nom, barre colorée et valeur
+            let rows = [
+                (
+                    "CPU",
+                    host.cpu,
+                    host.cpu.map(|cpu| thresholds.level(cpu as f64)),
+                    100.0,
+                ),
+                (
+                    "Mem",
+                    host.memory,
+                    host.memory.map(|memory| thresholds.level(memory as f64)),
+                    100.0,
+                ),
+                (
+                    "Disk",
+                    host.disk,
+                    host.disk.map(|disk| thresholds.level(disk as f64)),
+                    100.0,
+                ),
+                (
+                    "Temp",
+                    host.temperature,
+                    host.temperature
+                        .map(|celsius| thresholds.temperature_level(celsius as f64)),
+                    thresholds.temperature_critical.max(1.0) as f32 * 1.2,
+                ),
+            ];
+            let bar_left = left + ui.calc_text_size("Temp")[0] + padding[0] * 2.0;
+            let bar_right = right - ui.calc_text_size("100.0 °F")[0] - padding[0] * 2.0;
+            for (label, value, level, scale) in rows {
+                draw_list.add_text([left, y], text, label);
+                let bar_top = y + line_height * 0.25;
+                let bar_bottom = y + line_height * 0.75;
+                draw_list
+                    .add_rect([bar_left, bar_top], [bar_right, bar_bottom], disabled)
+                    .build();
+                let value_text = match (label, value) {
+                    (_, None) => "N/A".to_string(),
+                    ("Temp", Some(celsius)) => {
+                        format!("{:.1} {}", unit.convert(celsius), unit.symbol())
+                    }
+                    (_, Some(percent)) => format!("{:.1}%", percent),
+                };
+                if let (Some(value), Some(level)) = (value, level) {
+                    let fill = (value / scale).clamp(0.0, 1.0);
+                    let color = if connected {
+                        level.color(palette)
+                    } else {
+                        disabled
+                    };
+                    draw_list
+                        .add_rect(
+                            [bar_left, bar_top],
+                            [bar_left + (bar_right - bar_left) * fill, bar_bottom],
+                            color,
+                        )
+                        .filled(true)
+                        .build();
+                }
+                let value_x = right - ui.calc_text_size(&value_text)[0];
+                draw_list.add_text([value_x, y], text, &value_text);
+                y += line_height;
+            }
+
+            let (status, status_color) = match (&host.status, host.alerts) {
+                (RemoteStatus::Connecting, _) => ("Connecting...".to_string(), disabled),
+                (RemoteStatus::Disconnected(_), _) => ("Disconnected".to_string(), disabled),
+                (RemoteStatus::Connected, 0) => ("No alert firing".to_string(), text),
+                (RemoteStatus::Connected, alerts) => (
+                    format!("{} alert(s) firing", alerts),
+                    ThresholdLevel::Critical.color(palette),
+                ),
+            };
+            draw_list.add_text([left, y + line_height * 0.2], status_color, status);
+
+            if hovered {
+                ui.tooltip(|| {
+                    ui.text(&host.source);
+                    match &host.status {
+                        RemoteStatus::Disconnected(error) => {
+                            ui.text(format!("Disconnected: {}", error))
+                        }
+                        RemoteStatus::Connecting => ui.text("Connecting"),
+                        RemoteStatus::Connected => {}
+                    }
+                    if let Some(updated) = host.updated {
+                        ui.text(format!(
+                            "Updated: {}",
+                            updated.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+                        ));
+                    }
+                    ui.text_disabled("Click to show this host in the other windows");
+                });
+            }
+        }
+        clicked
+    }
+}
@@ -1,5 +1,9 @@
 pub mod graph {
+    use chrono::{DateTime, Utc};
     use imgui::Ui;
+    use serde::{Deserialize, Serialize};
+    use std::collections::VecDeque;
+    use std::fmt;
     use std::str::FromStr;
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, Instant};
@@ -7,13 +11,15 @@ pub mod graph {
     use sysinfo::{Components, System};
     use tokio::time::interval;
 
+    use crate::{format_duration, parse_duration, HistoryStore, Plot, PlotView};
+
     pub struct Cpu {
         pub temperatures: f32,
         pub fan_info: usize,
     }
 
     // Structure pour stocker les informations sur le ventilateur
-    #[derive(Debug)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct FanInfo {
         pub rpm: Option<i32>,
         pub min_rpm: Option<i32>,
@@ -21,6 +27,13 @@ pub mod graph {
         pub state: Option<String>,
     }
 
+    // Température d'un capteur (label sysinfo, ex: "coretemp Package id 0")
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SensorReading {
+        pub label: String,
+        pub temperature: f32,
+    }
+
     impl Cpu {
         pub fn new() -> Cpu {
             Self {
@@ -43,11 +56,20 @@ pub mod graph {
             tmp
         }
 
+        pub fn get_all_temperatures() -> Vec<SensorReading> {
+            Components::new_with_refreshed_list()
+                .iter()
+                .map(|component| SensorReading {
+                    label: component.label().to_string(),
+                    temperature: component.temperature(),
+                })
+                .collect()
+        }
+
         pub fn get_all_fan_info() -> io::Result<Vec<FanInfo>> {
             // Exécutez la commande sensors
-            let output = Command::new("sensors")
-                .output()
-                .expect("Erreur lors de l'exécution de la commande sensors");
+            // lm-sensors peut être absent (conteneurs, serveurs): on renvoie l'erreur
+            let output = Command::new("sensors").output()?;
 
             // Vérifiez si la commande a été exécutée avec succès
             if !output.status.success() {
@@ -81,7 +103,7 @@ pub mod graph {
                     }
 
                     // Vérifiez l'état du ventilateur (on/off)
-                    fan_info.state = Some(if fan_info.rpm.unwrap() > 0 {
+                    fan_info.state = Some(if fan_info.rpm.unwrap_or(0) > 0 {
                         "On".to_string()
                     } else {
                         "Off".to_string()
@@ -94,6 +116,15 @@ pub mod graph {
             Ok(fan_info_list)
         }
 
+        // Mesure bloquante: sysinfo a besoin de deux rafraîchissements espacés
+        pub fn measure_cpu_usage() -> f32 {
+            let mut sys = System::new();
+            sys.refresh_cpu_usage();
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+            sys.refresh_cpu_usage();
+            sys.global_cpu_info().cpu_usage()
+        }
+
         pub fn get_cpu_usage() -> f32 {
             let mut sys = System::new_all();
             // First we update all information of our `System` struct.
@@ -103,54 +134,568 @@ pub mod graph {
         }
     }
 
+    // Réglages d'un graphique modifiables dans l'interface
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct GraphSettings {
+        pub is_paused: bool,
+        pub fps: f32,
+        pub y_scale: f32,
+    }
+
+    // Échantillon horodaté d'un graphique
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct Sample {
+        pub timestamp: DateTime<Utc>,
+        pub value: f32,
+    }
+
+    // Agrégat des échantillons d'un intervalle (un échantillon brut = un agrégat de 1)
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Bucket {
+        pub start: DateTime<Utc>,
+        pub min: f32,
+        pub max: f32,
+        pub sum: f64,
+        pub count: u32,
+    }
+
+    impl Bucket {
+        pub fn from_sample(sample: &Sample) -> Bucket {
+            Bucket {
+                start: sample.timestamp,
+                min: sample.value,
+                max: sample.value,
+                sum: sample.value as f64,
+                count: 1,
+            }
+        }
+
+        // Un intervalle vide (count = 0) porte la dernière valeur connue dans min et max
+        pub fn avg(&self) -> f32 {
+            if self.count == 0 {
+                return self.min;
+            }
+            (self.sum / self.count as f64) as f32
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.count == 0
+        }
+
+        fn merge(&mut self, other: &Bucket) {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+            self.sum += other.sum;
+            self.count += other.count;
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct GraphStats {
+        pub min: f32,
+        pub max: f32,
+        pub avg: f32,
+        pub last: f32,
+        pub count: usize,
+    }
+
+    // Historique long à résolution réduite, ex: "1h@1s" (1 heure à 1 s), "7d@1m" (1 semaine à 1 min)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ArchiveSpec {
+        pub span: Duration,
+        pub resolution: Duration,
+    }
+
+    impl ArchiveSpec {
+        pub fn capacity(&self) -> usize {
+            (self.span.as_millis() / self.resolution.as_millis().max(1)).max(1) as usize
+        }
+    }
+
+    impl FromStr for ArchiveSpec {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (span, resolution) = s
+                .split_once('@')
+                .ok_or_else(|| format!("expected <span>@<resolution> (e.g. 1h@1s), got '{}'", s))?;
+            let spec = ArchiveSpec {
+                span: parse_duration(span)?,
+                resolution: parse_duration(resolution)?,
+            };
+            // Les archives sont alignées sur des millisecondes entières
+            if spec.resolution < Duration::from_millis(1) {
+                return Err(format!(
+                    "invalid archive '{}': resolution must be at least 1ms",
+                    s
+                ));
+            }
+            if spec.span < spec.resolution {
+                return Err(format!("invalid archive '{}'", s));
+            }
+            Ok(spec)
+        }
+    }
+
+    impl fmt::Display for ArchiveSpec {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "{}@{}",
+                format_duration(self.span),
+                format_duration(self.resolution)
+            )
+        }
+    }
+
+    impl Serialize for ArchiveSpec {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.to_string())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ArchiveSpec {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let text = String::deserialize(deserializer)?;
+            text.parse().map_err(serde::de::Error::custom)
+        }
+    }
+
+    // Tampon circulaire d'agrégats alignés sur la résolution
+    #[derive(Debug, Clone)]
+    pub struct Archive {
+        pub spec: ArchiveSpec,
+        pub buckets: VecDeque<Bucket>,
+    }
+
+    impl Archive {
+        pub fn new(spec: ArchiveSpec) -> Archive {
+            Archive {
+                spec,
+                buckets: VecDeque::with_capacity(spec.capacity()),
+            }
+        }
+
+        pub fn push(&mut self, sample: &Sample) {
+            let resolution = self.spec.resolution.as_millis().max(1) as i64;
+            let millis = sample.timestamp.timestamp_millis();
+            let start = DateTime::from_timestamp_millis(millis - millis.rem_euclid(resolution))
+                .unwrap_or(sample.timestamp);
+            match self.buckets.back_mut() {
+                Some(bucket) if bucket.start == start => bucket.merge(&Bucket::from_sample(sample)),
+                _ => {
+                    let mut bucket = Bucket::from_sample(sample);
+                    bucket.start = start;
+                    self.buckets.push_back(bucket);
+                    while self.buckets.len() > self.spec.capacity() {
+                        self.buckets.pop_front();
+                    }
+                }
+            }
+        }
+    }
+
+    // Regroupe des agrégats en au plus `points` intervalles de temps égaux entre le
+    // premier et le dernier; un intervalle vide reprend la valeur précédente, sans échantillon
+    pub fn downsample(buckets: &[Bucket], points: usize) -> Vec<Bucket> {
+        let (Some(first), Some(last)) = (buckets.first(), buckets.last()) else {
+            return Vec::new();
+        };
+        let points = points.max(1);
+        if buckets.len() <= points {
+            return buckets.to_vec();
+        }
+        let span = (last.start - first.start).num_milliseconds().max(1) as f64;
+        let mut bins: Vec<Option<Bucket>> = vec![None; points];
+        for bucket in buckets {
+            let offset = (bucket.start - first.start).num_milliseconds() as f64;
+            let index = ((offset / span) * (points - 1) as f64).round() as usize;
+            match &mut bins[index.min(points - 1)] {
+                Some(bin) => bin.merge(bucket),
+                bin => *bin = Some(*bucket),
+            }
+        }
+        let mut result = Vec::with_capacity(points);
+        for (index, bin) in bins.into_iter().enumerate() {
+            match bin {
+                Some(bin) => result.push(bin),
+                None => {
+                    if let Some(previous) = result.last().copied() {
+                        let value = previous.avg();
+                        result.push(Bucket {
+                            start: first.start
+                                + chrono::Duration::milliseconds(
+                                    (span * index as f64 / (points - 1) as f64) as i64,
+                                ),
+                            min: value,
+                            max: value,
+                            sum: 0.0,
+                            count: 0,
+                        });
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    // Les intervalles vides ajoutés par downsample ne comptent pas
+    pub fn compute_stats(buckets: &[Bucket]) -> Option<GraphStats> {
+        let last = buckets.iter().rev().find(|bucket| !bucket.is_empty())?;
+        let (min, max, sum, count) = buckets.iter().filter(|bucket| !bucket.is_empty()).fold(
+            (f32::MAX, f32::MIN, 0.0, 0u64),
+            |(min, max, sum, count), bucket| {
+                (
+                    min.min(bucket.min),
+                    max.max(bucket.max),
+                    sum + bucket.sum,
+                    count + bucket.count as u64,
+                )
+            },
+        );
+        Some(GraphStats {
+            min,
+            max,
+            avg: (sum / count.max(1) as f64) as f32,
+            last: last.avg(),
+            count: count as usize,
+        })
+    }
+
+    // Percentile (rang le plus proche) des moyennes des agrégats, p entre 0 et 100
+    pub fn compute_percentile(buckets: &[Bucket], p: f32) -> Option<f32> {
+        let mut values: Vec<f32> = buckets
+            .iter()
+            .filter(|bucket| !bucket.is_empty())
+            .map(Bucket::avg)
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f32::total_cmp);
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * values.len() as f32).ceil() as usize;
+        Some(values[rank.saturating_sub(1).min(values.len() - 1)])
+    }
+
+    // Plage affichée par un graphique
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub enum GraphView {
+        // Échantillons récents
+        #[default]
+        Live,
+        // Index d'une archive en mémoire
+        Archive(usize),
+        // Dernière période lue dans l'historique persistant
+        History(Duration),
+    }
+
+    // Lien d'un graphique avec l'historique persistant
+    pub struct GraphHistory {
+        pub metric: String,
+        pub store: Arc<HistoryStore>,
+        // Dernière lecture, refaite quand la plage change ou après HISTORY_REFRESH
+        pub buckets: Vec<Bucket>,
+        pub loaded: Option<(GraphView, Instant)>,
+        pub error: Option<String>,
+    }
+
+    const HISTORY_REFRESH: Duration = Duration::from_secs(5);
+
     pub struct GraphData {
-        pub data: Arc<Mutex<Vec<f32>>>,
+        // Échantillons récents, du plus ancien au plus récent (au plus max_points)
+        pub samples: VecDeque<Sample>,
         pub max_points: usize,
         pub update_interval: Duration,
         pub last_update: Instant,
         pub is_paused: bool,
         pub fps: f32,
         pub y_scale: f32,
+        // Horodatage des événements marqués
+        pub markers: Vec<DateTime<Utc>>,
+        pub marker_color: [f32; 4],
+        pub archives: Vec<Archive>,
+        pub view: GraphView,
+        pub history: Option<GraphHistory>,
+        // Zoom et défilement du graphique
+        pub plot_view: PlotView,
     }
 
     impl GraphData {
         pub fn new(max_points: usize, update_interval: Duration) -> Self {
             Self {
-                data: Arc::new(Mutex::new(Vec::with_capacity(max_points))),
+                samples: VecDeque::with_capacity(max_points),
                 max_points,
                 update_interval,
                 last_update: Instant::now(),
                 is_paused: false,
-                fps: 1.0,
+                fps: 1.0 / update_interval.as_secs_f32(),
                 y_scale: 1.0,
+                markers: Vec::new(),
+                marker_color: [1.0, 0.3, 0.3, 1.0],
+                archives: Vec::new(),
+                view: GraphView::Live,
+                history: None,
+                plot_view: PlotView::default(),
+            }
+        }
+
+        // Applique l'intervalle et la longueur d'historique de la configuration
+        pub fn configure(&mut self, update_interval: Duration, max_points: usize) {
+            self.update_interval = update_interval;
+            self.fps = 1.0 / update_interval.as_secs_f32();
+            self.max_points = max_points;
+            while self.samples.len() > max_points {
+                self.samples.pop_front();
+            }
+        }
+
+        // Les archives inchangées gardent leur contenu
+        pub fn set_archives(&mut self, specs: &[ArchiveSpec]) {
+            let mut previous = std::mem::take(&mut self.archives);
+            self.archives = specs
+                .iter()
+                .map(|spec| match previous.iter().position(|a| a.spec == *spec) {
+                    Some(index) => previous.swap_remove(index),
+                    None => Archive::new(*spec),
+                })
+                .collect();
+            if matches!(self.view, GraphView::Archive(index) if index >= self.archives.len()) {
+                self.view = GraphView::Live;
+            }
+        }
+
+        // Les échantillons sont aussi enregistrés sous `metric` dans l'historique persistant
+        pub fn set_history(&mut self, metric: &str, store: Arc<HistoryStore>) {
+            self.history = Some(GraphHistory {
+                metric: metric.to_string(),
+                store,
+                buckets: Vec::new(),
+                loaded: None,
+                error: None,
+            });
+        }
+
+        pub fn settings(&self) -> GraphSettings {
+            GraphSettings {
+                is_paused: self.is_paused,
+                fps: self.fps,
+                y_scale: self.y_scale,
             }
         }
 
+        pub fn apply_settings(&mut self, settings: &GraphSettings) {
+            self.is_paused = settings.is_paused;
+            if settings.fps > 0.0 {
+                self.fps = settings.fps;
+            }
+            self.y_scale = settings.y_scale.clamp(1.0, 10.0);
+            self.adjust_interval();
+        }
+
+        // Recalcule l'intervalle de mise à jour à partir du FPS choisi
+        pub fn adjust_interval(&mut self) {
+            self.update_interval = Duration::from_secs_f32(1.0 / self.fps);
+        }
+
         pub fn update(&mut self, new_value: f32) {
-            if !self.is_paused {
-                let mut data = self.data.lock().unwrap();
-                data.push(new_value);
-                if data.len() >= self.max_points {
-                    data.remove(0);
+            self.push(Sample {
+                timestamp: Utc::now(),
+                value: new_value,
+            });
+        }
+
+        pub fn push(&mut self, sample: Sample) {
+            if self.is_paused {
+                return;
+            }
+            self.samples.push_back(sample);
+            while self.samples.len() > self.max_points {
+                self.samples.pop_front();
+            }
+            for archive in self.archives.iter_mut() {
+                archive.push(&sample);
+            }
+            if let Some(history) = &self.history {
+                history.store.record(&history.metric, &sample);
+            }
+            if let Some(oldest) = self.oldest_timestamp() {
+                self.markers.retain(|&marker| marker >= oldest);
+            }
+        }
+
+        // Remplace tout l'historique (ex: relecture d'une session enregistrée)
+        pub fn set_samples(&mut self, samples: Vec<Sample>) {
+            self.markers.clear();
+            for archive in self.archives.iter_mut() {
+                archive.buckets.clear();
+                for sample in &samples {
+                    archive.push(sample);
                 }
             }
+            let skip = samples.len().saturating_sub(self.max_points);
+            self.samples = samples.into_iter().skip(skip).collect();
         }
-        // Votre fonction pour tracer les graphiques
-        pub fn draw_graph(&self, ui: &Ui, label: &str, hover: &str) {
-            let data = self.data.lock().unwrap();
-            let (min, max) = data.iter().fold((f32::MAX, f32::MIN), |(min, max), &val| {
-                (min.min(val), max.max(val))
-            });
-            let last_value = data.last().unwrap_or(&0.0);
-            let last_value_str = format!("{:.2}", last_value);
-            let overlay_text = hover.replace('#', &last_value_str);
 
-            ui.plot_lines(label, &data)
-                .graph_size([500.0, 100.0])
-                .scale_min(min)
-                .scale_max(max * self.y_scale)
-                .overlay_text(overlay_text)
-                .build();
+        // Plus ancien instant encore représenté (échantillons ou archives)
+        fn oldest_timestamp(&self) -> Option<DateTime<Utc>> {
+            self.archives
+                .iter()
+                .filter_map(|archive| archive.buckets.front().map(|bucket| bucket.start))
+                .chain(self.samples.front().map(|sample| sample.timestamp))
+                .min()
+        }
+
+        // Marque un instant (ex: un événement du noyau), ignoré s'il précède l'historique
+        pub fn mark_at(&mut self, timestamp: DateTime<Utc>) {
+            if self
+                .oldest_timestamp()
+                .is_some_and(|oldest| timestamp >= oldest)
+            {
+                self.markers.push(timestamp);
+            }
+        }
+
+        pub fn values(&self) -> Vec<f32> {
+            self.samples.iter().map(|sample| sample.value).collect()
+        }
+
+        pub fn last_value(&self) -> Option<f32> {
+            self.samples.back().map(|sample| sample.value)
+        }
+
+        // Agrégats de la plage affichée
+        pub fn view_buckets(&self) -> Vec<Bucket> {
+            match self.view {
+                GraphView::Archive(index) if index < self.archives.len() => {
+                    self.archives[index].buckets.iter().copied().collect()
+                }
+                GraphView::History(_) => self
+                    .history
+                    .as_ref()
+                    .map(|history| history.buckets.clone())
+                    .unwrap_or_default(),
+                _ => self.samples.iter().map(Bucket::from_sample).collect(),
+            }
+        }
+
+        // Change la plage affichée (ex: pour suivre celle d'un autre graphique)
+        pub fn set_view(&mut self, view: GraphView) {
+            self.view = view;
+            self.refresh_history();
+        }
+
+        // Relit l'historique persistant si la plage affichée en vient
+        pub fn refresh_history(&mut self) {
+            let (GraphView::History(range), Some(history)) = (self.view, self.history.as_mut())
+            else {
+                return;
+            };
+            let stale = match history.loaded {
+                Some((view, loaded)) => view != self.view || loaded.elapsed() >= HISTORY_REFRESH,
+                None => true,
+            };
+            if !stale {
+                return;
+            }
+            let now = Utc::now();
+            let since = now - chrono::Duration::from_std(range).unwrap_or(chrono::Duration::MAX);
+            match history
+                .store
+                .query(&history.metric, since, now, Plot::WIDTH as usize)
+            {
+                Ok(buckets) => {
+                    history.buckets = buckets;
+                    history.error = None;
+                }
+                Err(e) => history.error = Some(e),
+            }
+            history.loaded = Some((self.view, Instant::now()));
+        }
+
+        // Agrégats couvrant la dernière `window`: échantillons récents s'ils
+        // remontent assez loin, sinon l'archive la plus fine qui la couvre
+        pub fn window_buckets(&self, window: Duration) -> Vec<Bucket> {
+            let Some(last) = self.samples.back().map(|sample| sample.timestamp) else {
+                return Vec::new();
+            };
+            let since = last - chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX);
+            let covered = |start: Option<DateTime<Utc>>| start.is_some_and(|start| start <= since);
+            let buckets: Vec<Bucket> = if covered(self.samples.front().map(|s| s.timestamp)) {
+                self.samples.iter().map(Bucket::from_sample).collect()
+            } else {
+                match self
+                    .archives
+                    .iter()
+                    .filter(|archive| archive.spec.span >= window)
+                    .min_by_key(|archive| archive.spec.resolution)
+                    .or_else(|| self.archives.iter().max_by_key(|archive| archive.spec.span))
+                {
+                    Some(archive) => archive.buckets.iter().copied().collect(),
+                    None => self.samples.iter().map(Bucket::from_sample).collect(),
+                }
+            };
+            buckets
+                .into_iter()
+                .filter(|bucket| bucket.start >= since)
+                .collect()
+        }
+
+        pub fn stats(&self, window: Duration) -> Option<GraphStats> {
+            compute_stats(&self.window_buckets(window))
+        }
+
+        pub fn percentile(&self, p: f32, window: Duration) -> Option<f32> {
+            compute_percentile(&self.window_buckets(window), p)
+        }
+
+        // Choix de la plage: échantillons récents, une des archives ou l'historique persistant
+        pub fn draw_range_selector(&mut self, ui: &Ui) {
+            let ranges = self
+                .history
+                .as_ref()
+                .map(|history| history.store.ranges())
+                .unwrap_or_default();
+            if self.archives.is_empty() && ranges.is_empty() {
+                return;
+            }
+            let live = match (self.samples.front(), self.samples.back()) {
+                (Some(first), Some(last)) => format!(
+                    "Last {}",
+                    format_duration(Duration::from_secs(
+                        (last.timestamp - first.timestamp).num_seconds().max(0) as u64
+                    ))
+                ),
+                _ => "Recent".to_string(),
+            };
+            let mut views = vec![GraphView::Live];
+            let mut labels = vec![live];
+            for (index, archive) in self.archives.iter().enumerate() {
+                views.push(GraphView::Archive(index));
+                labels.push(format!(
+                    "{} at {}",
+                    format_duration(archive.spec.span),
+                    format_duration(archive.spec.resolution)
+                ));
+            }
+            for range in ranges {
+                views.push(GraphView::History(range));
+                labels.push(format!("History: last {}", format_duration(range)));
+            }
+            let mut index = views
+                .iter()
+                .position(|view| *view == self.view)
+                .unwrap_or(0);
+            if ui.combo_simple_string("Range", &mut index, &labels) {
+                self.view = views[index];
+            }
+            self.refresh_history();
+            if let Some(error) = self
+                .history
+                .as_ref()
+                .and_then(|history| history.error.as_ref())
+            {
+                ui.text_disabled(format!("History error: {}", error));
+            }
         }
     }
 
@@ -181,10 +726,13 @@ pub mod graph {
         ));
         loop {
             interval.tick().await;
-            let fan_info_list = Cpu::get_all_fan_info().unwrap();
+            let rpm = Cpu::get_all_fan_info()
+                .ok()
+                .and_then(|fans| fans.first().and_then(|fan| fan.rpm))
+                .unwrap_or(0);
             {
                 let mut graph = graph_data.lock().unwrap();
-                graph.update(fan_info_list[0].rpm.unwrap_or(0) as f32);
+                graph.update(rpm as f32);
             }
         }
     }
@@ -212,12 +760,8 @@ pub mod graph {
         fan_graph: Arc<Mutex<GraphData>>,
         temp_graph: Arc<Mutex<GraphData>>,
     ) {
-        let fps_cpu = cpu_graph.lock().unwrap().fps;
-        let fps_fan = fan_graph.lock().unwrap().fps;
-        let fps_temp = temp_graph.lock().unwrap().fps;
-
-        cpu_graph.lock().unwrap().update_interval = Duration::from_secs_f32(1.0 / fps_cpu);
-        fan_graph.lock().unwrap().update_interval = Duration::from_secs_f32(1.0 / fps_fan);
-        temp_graph.lock().unwrap().update_interval = Duration::from_secs_f32(1.0 / fps_temp);
+        cpu_graph.lock().unwrap().adjust_interval();
+        fan_graph.lock().unwrap().adjust_interval();
+        temp_graph.lock().unwrap().adjust_interval();
     }
 }
@@ -0,0 +1,275 @@
+pub mod history {
+    use chrono::{DateTime, TimeZone, Utc};
+    use rusqlite::{params, Connection};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+    use std::sync::{Arc, Mutex};
+    use std::thread::{self, JoinHandle};
+    use std::time::{Duration, Instant};
+
+    use crate::{ArchiveSpec, Bucket, Sample};
+
+    // Plages proposées dans la liste "Range" des graphiques
+    pub const HISTORY_RANGES: [Duration; 4] = [
+        Duration::from_secs(3600),
+        Duration::from_secs(86400),
+        Duration::from_secs(7 * 86400),
+        Duration::from_secs(30 * 86400),
+    ];
+
+    // Fréquence de suppression des agrégats sortis de leur durée de conservation
+    const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+    // Échantillons gardés en mémoire quand la base est inaccessible
+    const MAX_PENDING: usize = 100_000;
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS buckets (
+            metric TEXT NOT NULL,
+            resolution INTEGER NOT NULL,
+            start INTEGER NOT NULL,
+            min_value REAL NOT NULL,
+            max_value REAL NOT NULL,
+            sum_value REAL NOT NULL,
+            sample_count INTEGER NOT NULL,
+            PRIMARY KEY (metric, resolution, start)
+        ) WITHOUT ROWID;
+    ";
+
+    // Un échantillon compte dans l'agrégat de chaque niveau de conservation
+    const UPSERT: &str = "
+        INSERT INTO buckets VALUES (?1, ?2, ?3, ?4, ?4, ?4, 1)
+        ON CONFLICT (metric, resolution, start) DO UPDATE SET
+            min_value = MIN(min_value, excluded.min_value),
+            max_value = MAX(max_value, excluded.max_value),
+            sum_value = sum_value + excluded.sum_value,
+            sample_count = sample_count + 1
+    ";
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct HistoryStatus {
+        pub written: u64,
+        pub last_flush: Option<DateTime<Utc>>,
+        pub error: Option<String>,
+    }
+
+    // Base SQLite des échantillons des graphiques: l'écriture se fait par lots dans un
+    // thread dédié, la lecture avec une connexion séparée
+    pub struct HistoryStore {
+        pub path: PathBuf,
+        pub retention: Vec<ArchiveSpec>,
+        pub status: Arc<Mutex<HistoryStatus>>,
+        sender: Option<Sender<(String, Sample)>>,
+        worker: Option<JoinHandle<()>>,
+        reader: Mutex<Connection>,
+    }
+
+    fn open_connection(path: &Path) -> Result<Connection, String> {
+        let connection =
+            Connection::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .and_then(|_| connection.busy_timeout(Duration::from_secs(5)))
+            .and_then(|_| connection.execute_batch(SCHEMA))
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(connection)
+    }
+
+    impl HistoryStore {
+        pub fn open(
+            path: &Path,
+            retention: &[ArchiveSpec],
+            flush_interval: Duration,
+        ) -> Result<HistoryStore, String> {
+            if let Some(parent) = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty())
+            {
+                fs::create_dir_all(parent).map_err(|e| format!("{}: {}", parent.display(), e))?;
+            }
+            let writer = open_connection(path)?;
+            let reader = open_connection(path)?;
+            let status = Arc::new(Mutex::new(HistoryStatus::default()));
+            let (sender, receiver) = mpsc::channel();
+            let worker = {
+                let retention = retention.to_vec();
+                let status = status.clone();
+                thread::spawn(move || {
+                    write_loop(writer, receiver, &retention, flush_interval, &status)
+                })
+            };
+            Ok(HistoryStore {
+                path: path.to_path_buf(),
+                retention: retention.to_vec(),
+                status,
+                sender: Some(sender),
+                worker: Some(worker),
+                reader: Mutex::new(reader),
+            })
+        }
+
+        pub fn record(&self, metric: &str, sample: &Sample) {
+            if let Some(sender) = &self.sender {
+                let _ = sender.send((metric.to_string(), *sample));
+            }
+        }
+
+        // Plages consultables avec les durées de conservation configurées
+        pub fn ranges(&self) -> Vec<Duration> {
+            let longest = self.retention.iter().map(|spec| spec.span).max();
+            HISTORY_RANGES
+                .into_iter()
+                .filter(|range| longest.is_some_and(|longest| *range <= longest))
+                .collect()
+        }
+
+        // Agrégats de `metric` entre `since` et `until`, regroupés en au plus `points`
+        // intervalles, lus dans le niveau le plus fin qui remonte jusqu'à `since`
+        pub fn query(
+            &self,
+            metric: &str,
+            since: DateTime<Utc>,
+            until: DateTime<Utc>,
+            points: usize,
+        ) -> Result<Vec<Bucket>, String> {
+            let age = (Utc::now() - since).to_std().unwrap_or_default();
+            let Some(spec) = self
+                .retention
+                .iter()
+                .filter(|spec| spec.span >= age)
+                .min_by_key(|spec| spec.resolution)
+                .or_else(|| self.retention.iter().max_by_key(|spec| spec.span))
+            else {
+                return Ok(Vec::new());
+            };
+            let resolution = spec.resolution.as_millis() as i64;
+            let (since, until) = (since.timestamp_millis(), until.timestamp_millis());
+            let bin = ((until - since) / points.max(1) as i64).max(resolution);
+            let connection = self.reader.lock().unwrap();
+            let mut statement = connection
+                .prepare_cached(
+                    "SELECT MIN(start), MIN(min_value), MAX(max_value), SUM(sum_value), SUM(sample_count)
+                     FROM buckets
+                     WHERE metric = ?1 AND resolution = ?2 AND start >= ?3 AND start <= ?4
+                     GROUP BY (start - ?3) / ?5
+                     ORDER BY 1",
+                )
+                .map_err(|e| e.to_string())?;
+            let rows = statement
+                .query_map(params![metric, resolution, since, until, bin], |row| {
+                    Ok(Bucket {
+                        start: Utc
+                            .timestamp_millis_opt(row.get(0)?)
+                            .single()
+                            .unwrap_or_default(),
+                        min: row.get::<_, f64>(1)? as f32,
+                        max: row.get::<_, f64>(2)? as f32,
+                        sum: row.get(3)?,
+                        count: row.get(4)?,
+                    })
+                })
+                .map_err(|e| e.to_string())?;
+            rows.collect::<Result<Vec<Bucket>, _>>()
+                .map_err(|e| e.to_string())
+        }
+    }
+
+    // Le dernier lot est écrit avant la fermeture
+    impl Drop for HistoryStore {
+        fn drop(&mut self) {
+            self.sender.take();
+            if let Some(worker) = self.worker.take() {
+                let _ = worker.join();
+            }
+        }
+    }
+
+    fn write_loop(
+        mut connection: Connection,
+        receiver: Receiver<(String, Sample)>,
+        retention: &[ArchiveSpec],
+        flush_interval: Duration,
+        status: &Mutex<HistoryStatus>,
+    ) {
+        let mut pending = Vec::new();
+        let mut last_flush = Instant::now();
+        let mut last_prune: Option<Instant> = None;
+        loop {
+            let timeout = flush_interval.saturating_sub(last_flush.elapsed());
+            let disconnected = match receiver.recv_timeout(timeout) {
+                Ok(record) => {
+                    pending.push(record);
+                    false
+                }
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => true,
+            };
+            if !disconnected && last_flush.elapsed() < flush_interval {
+                continue;
+            }
+            let prune = last_prune.is_none_or(|last| last.elapsed() >= PRUNE_INTERVAL);
+            let result = flush(&mut connection, &pending, retention, prune);
+            let mut status = status.lock().unwrap();
+            match result {
+                Ok(()) => {
+                    status.written += pending.len() as u64;
+                    status.last_flush = Some(Utc::now());
+                    status.error = None;
+                    pending.clear();
+                    if prune {
+                        last_prune = Some(Instant::now());
+                    }
+                }
+                // Le lot est conservé pour la prochaine tentative, dans une limite
+                Err(e) => {
+                    status.error = Some(e);
+                    let excess = pending.len().saturating_sub(MAX_PENDING);
+                    pending.drain(..excess);
+                }
+            }
+            last_flush = Instant::now();
+            if disconnected {
+                return;
+            }
+        }
+    }
+
+    fn flush(
+        connection: &mut Connection,
+        records: &[(String, Sample)],
+        retention: &[ArchiveSpec],
+        prune: bool,
+    ) -> Result<(), String> {
+        let transaction = connection.transaction().map_err(|e| e.to_string())?;
+        {
+            let mut upsert = transaction
+                .prepare_cached(UPSERT)
+                .map_err(|e| e.to_string())?;
+            for (metric, sample) in records {
+                let timestamp = sample.timestamp.timestamp_millis();
+                for spec in retention {
+                    let resolution = spec.resolution.as_millis() as i64;
+                    let start = timestamp - timestamp.rem_euclid(resolution);
+                    upsert
+                        .execute(params![metric, resolution, start, sample.value as f64])
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            if prune {
+                let now = Utc::now().timestamp_millis();
+                for spec in retention {
+                    transaction
+                        .execute(
+                            "DELETE FROM buckets WHERE resolution = ?1 AND start < ?2",
+                            params![
+                                spec.resolution.as_millis() as i64,
+                                now - spec.span.as_millis() as i64
+                            ],
+                        )
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+        }
+        transaction.commit().map_err(|e| e.to_string())
+    }
+}
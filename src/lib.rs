@@ -7,4 +7,48 @@ pub use memories::memory_util::*;
 pub mod netwok;
 pub use netwok::network::*;
 pub mod process;
-pub use process::process::*;
\ No newline at end of file
+pub use process::process::*;
+pub mod events;
+pub use events::event::*;
+pub mod commands;
+pub use commands::command::*;
+pub mod reports;
+pub use reports::report::*;
+pub mod tui;
+pub use tui::terminal::*;
+pub mod snapshots;
+pub use snapshots::snapshot::*;
+pub mod exporter;
+pub use exporter::prometheus::*;
+pub mod recorder;
+pub use recorder::recording::*;
+pub mod sessions;
+pub use sessions::session::*;
+pub mod alerts;
+pub use alerts::alert::*;
+pub mod notifications;
+pub use notifications::notification::*;
+pub mod configs;
+pub use configs::config::*;
+pub mod preferences;
+pub use preferences::preference::*;
+pub mod themes;
+pub use themes::theme::*;
+pub mod plots;
+pub use plots::plot::*;
+pub mod histories;
+pub use histories::history::*;
+pub mod agents;
+pub use agents::agent::*;
+pub mod fleets;
+pub use fleets::fleet::*;
+pub mod cgroups;
+pub use cgroups::cgroup::*;
+pub mod services;
+pub use services::service::*;
+pub mod powers;
+pub use powers::power::*;
+pub mod energies;
+pub use energies::energy::*;
+pub mod logins;
+pub use logins::login::*;
\ No newline at end of file
@@ -0,0 +1,536 @@
+pub mod login {
+    use std::{
+        fs::{self, File},
+        io::{self, Read, Seek, SeekFrom},
+        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+        path::{Path, PathBuf},
+        time::{Instant, SystemTime},
+    };
+
+    use chrono::{DateTime, Local, TimeZone, Utc};
+    use imgui::{TreeNodeFlags, Ui};
+
+    pub const DEFAULT_UTMP: &str = "/var/run/utmp";
+    pub const DEFAULT_WTMP: &str = "/var/log/wtmp";
+
+    // struct utmp de la glibc sur Linux (x86_64, aarch64...): 384 octets
+    pub const UTMP_RECORD_SIZE: usize = 384;
+    const LINE_OFFSET: usize = 8;
+    const ID_OFFSET: usize = 40;
+    const USER_OFFSET: usize = 44;
+    const HOST_OFFSET: usize = 76;
+    const TIME_OFFSET: usize = 340;
+    const ADDRESS_OFFSET: usize = 348;
+    // wtmp peut dépasser plusieurs Mo: seule la fin est relue
+    const MAX_WTMP_RECORDS: u64 = 8192;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum UtmpKind {
+        Empty,
+        RunLevel,
+        BootTime,
+        NewTime,
+        OldTime,
+        InitProcess,
+        LoginProcess,
+        UserProcess,
+        DeadProcess,
+        Accounting,
+    }
+
+    impl UtmpKind {
+        fn from_raw(value: i16) -> Option<UtmpKind> {
+            Some(match value {
+                0 => UtmpKind::Empty,
+                1 => UtmpKind::RunLevel,
+                2 => UtmpKind::BootTime,
+                3 => UtmpKind::NewTime,
+                4 => UtmpKind::OldTime,
+                5 => UtmpKind::InitProcess,
+                6 => UtmpKind::LoginProcess,
+                7 => UtmpKind::UserProcess,
+                8 => UtmpKind::DeadProcess,
+                9 => UtmpKind::Accounting,
+                _ => return None,
+            })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct UtmpRecord {
+        pub kind: UtmpKind,
+        pub pid: i32,
+        // Terminal sans "/dev/", ex: pts/0
+        pub line: String,
+        pub id: String,
+        pub user: String,
+        pub host: String,
+        pub time: DateTime<Utc>,
+        pub address: Option<IpAddr>,
+    }
+
+    fn text(bytes: &[u8]) -> String {
+        let end = bytes
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).trim().to_string()
+    }
+
+    fn int32(bytes: &[u8], offset: usize) -> i32 {
+        i32::from_ne_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    impl UtmpRecord {
+        pub fn parse(bytes: &[u8]) -> Option<UtmpRecord> {
+            if bytes.len() < UTMP_RECORD_SIZE {
+                return None;
+            }
+            let kind = UtmpKind::from_raw(i16::from_ne_bytes([bytes[0], bytes[1]]))?;
+            let seconds = int32(bytes, TIME_OFFSET) as u32 as i64;
+            let micros = int32(bytes, TIME_OFFSET + 4).clamp(0, 999_999) as u32;
+            let time = Utc.timestamp_opt(seconds, micros * 1000).single()?;
+            // IPv4 dans le premier mot seulement, sinon IPv6 complète
+            let words: Vec<[u8; 4]> = (0..4)
+                .map(|index| {
+                    let offset = ADDRESS_OFFSET + index * 4;
+                    bytes[offset..offset + 4].try_into().unwrap()
+                })
+                .collect();
+            let address = if words.iter().all(|word| *word == [0; 4]) {
+                None
+            } else if words[1..].iter().all(|word| *word == [0; 4]) {
+                Some(IpAddr::V4(Ipv4Addr::from(words[0])))
+            } else {
+                let mut octets = [0u8; 16];
+                for (index, word) in words.iter().enumerate() {
+                    octets[index * 4..index * 4 + 4].copy_from_slice(word);
+                }
+                Some(IpAddr::V6(Ipv6Addr::from(octets)))
+            };
+            Some(UtmpRecord {
+                kind,
+                pid: int32(bytes, 4),
+                line: text(&bytes[LINE_OFFSET..ID_OFFSET]),
+                id: text(&bytes[ID_OFFSET..USER_OFFSET]),
+                user: text(&bytes[USER_OFFSET..HOST_OFFSET]),
+                host: text(&bytes[HOST_OFFSET..HOST_OFFSET + 256]),
+                time,
+                address,
+            })
+        }
+    }
+
+    // Enregistrements complets d'un fichier utmp/wtmp, ou seulement les `limit` derniers
+    pub fn read_utmp(path: &Path, limit: Option<u64>) -> io::Result<Vec<UtmpRecord>> {
+        let mut file = File::open(path)?;
+        let length = file.metadata()?.len();
+        let size = UTMP_RECORD_SIZE as u64;
+        let count = length / size;
+        let skip = limit.map(|limit| count.saturating_sub(limit)).unwrap_or(0);
+        file.seek(SeekFrom::Start(skip * size))?;
+        let mut content = Vec::new();
+        file.take((count - skip) * size).read_to_end(&mut content)?;
+        Ok(content
+            .chunks_exact(UTMP_RECORD_SIZE)
+            .filter_map(UtmpRecord::parse)
+            .collect())
+    }
+
+    // Session ouverte d'après utmp
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct LoginSession {
+        pub user: String,
+        pub line: String,
+        pub host: String,
+        pub pid: i32,
+        pub login_time: DateTime<Utc>,
+        // Dernière lecture du terminal (comme `w`), si /dev/<line> existe
+        pub idle: Option<chrono::Duration>,
+    }
+
+    pub fn terminal_idle(line: &str, now: SystemTime) -> Option<chrono::Duration> {
+        let accessed = fs::metadata(Path::new("/dev").join(line))
+            .and_then(|metadata| metadata.accessed())
+            .ok()?;
+        let idle = now.duration_since(accessed).unwrap_or_default();
+        chrono::Duration::from_std(idle).ok()
+    }
+
+    pub fn active_sessions(records: &[UtmpRecord]) -> Vec<LoginSession> {
+        let now = SystemTime::now();
+        let mut sessions: Vec<LoginSession> = records
+            .iter()
+            .filter(|record| record.kind == UtmpKind::UserProcess && !record.user.is_empty())
+            .map(|record| LoginSession {
+                user: record.user.clone(),
+                line: record.line.clone(),
+                host: record.host.clone(),
+                pid: record.pid,
+                login_time: record.time,
+                idle: terminal_idle(&record.line, now),
+            })
+            .collect();
+        sessions.sort_by_key(|session| session.login_time);
+        sessions
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum LoginEnd {
+        StillLoggedIn,
+        Logout(DateTime<Utc>),
+        // Redémarrage avant la déconnexion ("crash" pour `last`)
+        Reboot(DateTime<Utc>),
+    }
+
+    // Connexion ou démarrage de l'historique wtmp
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct LoginRecord {
+        pub user: String,
+        pub line: String,
+        pub host: String,
+        pub login_time: DateTime<Utc>,
+        pub end: LoginEnd,
+        pub reboot: bool,
+    }
+
+    impl LoginRecord {
+        pub fn duration(&self) -> Option<chrono::Duration> {
+            match self.end {
+                LoginEnd::Logout(end) | LoginEnd::Reboot(end) => Some(end - self.login_time),
+                LoginEnd::StillLoggedIn => None,
+            }
+        }
+    }
+
+    // Comme `last`: wtmp est parcouru à rebours, une déconnexion (DEAD_PROCESS) fermant la
+    // connexion précédente du même terminal, un démarrage fermant toutes les autres
+    pub fn login_history(records: &[UtmpRecord], limit: usize) -> Vec<LoginRecord> {
+        let mut history = Vec::new();
+        let mut logouts: Vec<(String, DateTime<Utc>)> = Vec::new();
+        let mut last_boot: Option<DateTime<Utc>> = None;
+        for record in records.iter().rev() {
+            if history.len() >= limit {
+                break;
+            }
+            match record.kind {
+                UtmpKind::DeadProcess if !record.line.is_empty() => {
+                    logouts.retain(|(line, _)| *line != record.line);
+                    logouts.push((record.line.clone(), record.time));
+                }
+                UtmpKind::UserProcess if !record.user.is_empty() => {
+                    let logout = logouts
+                        .iter()
+                        .position(|(line, _)| *line == record.line)
+                        .map(|index| logouts.remove(index).1);
+                    let end = match (logout, last_boot) {
+                        (Some(logout), _) => LoginEnd::Logout(logout),
+                        (None, Some(boot)) => LoginEnd::Reboot(boot),
+                        (None, None) => LoginEnd::StillLoggedIn,
+                    };
+                    history.push(LoginRecord {
+                        user: record.user.clone(),
+                        line: record.line.clone(),
+                        host: record.host.clone(),
+                        login_time: record.time,
+                        end,
+                        reboot: false,
+                    });
+                }
+                UtmpKind::BootTime => {
+                    history.push(LoginRecord {
+                        user: "reboot".to_string(),
+                        line: "system boot".to_string(),
+                        host: record.host.clone(),
+                        login_time: record.time,
+                        end: match last_boot {
+                            Some(boot) => LoginEnd::Reboot(boot),
+                            None => LoginEnd::StillLoggedIn,
+                        },
+                        reboot: true,
+                    });
+                    logouts.clear();
+                    last_boot = Some(record.time);
+                }
+                _ => {}
+            }
+        }
+        history
+    }
+
+    // Sessions ouvertes (utmp) et dernières connexions (wtmp), relues à intervalle régulier
+    pub struct LoginMonitor {
+        pub utmp: PathBuf,
+        pub wtmp: PathBuf,
+        pub history_length: usize,
+        pub sessions: Vec<LoginSession>,
+        pub history: Vec<LoginRecord>,
+        pub error: Option<String>,
+        pub last_refresh: Option<Instant>,
+    }
+
+    impl LoginMonitor {
+        pub fn new<P: Into<PathBuf>>(utmp: P, wtmp: P, history_length: usize) -> LoginMonitor {
+            LoginMonitor {
+                utmp: utmp.into(),
+                wtmp: wtmp.into(),
+                history_length,
+                sessions: Vec::new(),
+                history: Vec::new(),
+                error: None,
+                last_refresh: None,
+            }
+        }
+
+        // wtmp absent (conteneurs) n'empêche pas d'afficher les sessions ouvertes
+        pub fn refresh(&mut self) {
+            let mut errors = Vec::new();
+            match read_utmp(&self.utmp, None) {
+                Ok(records) => self.sessions = active_sessions(&records),
+                Err(e) => {
+                    self.sessions.clear();
+                    errors.push(format!("{}: {}", self.utmp.display(), e));
+                }
+            }
+            match read_utmp(&self.wtmp, Some(MAX_WTMP_RECORDS)) {
+                Ok(records) => self.history = login_history(&records, self.history_length),
+                Err(e) => {
+                    self.history.clear();
+                    errors.push(format!("{}: {}", self.wtmp.display(), e));
+                }
+            }
+            self.error = if errors.is_empty() {
+                None
+            } else {
+                Some(errors.join("; "))
+            };
+            self.last_refresh = Some(Instant::now());
+        }
+    }
+
+    fn format_span(duration: chrono::Duration) -> String {
+        let minutes = duration.num_minutes().max(0);
+        if minutes >= 1440 {
+            format!(
+                "{}d {:02}:{:02}",
+                minutes / 1440,
+                minutes % 1440 / 60,
+                minutes % 60
+            )
+        } else {
+            format!("{:02}:{:02}", minutes / 60, minutes % 60)
+        }
+    }
+
+    fn format_time(time: DateTime<Utc>) -> String {
+        time.with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    }
+
+    fn or_dash(value: &str) -> &str {
+        if value.is_empty() {
+            "-"
+        } else {
+            value
+        }
+    }
+
+    // Section "Sessions" de la fenêtre Système
+    pub fn draw_logins(ui: &Ui, monitor: &LoginMonitor) {
+        if !ui.collapsing_header(
+            format!("Sessions ({})", monitor.sessions.len()),
+            TreeNodeFlags::empty(),
+        ) {
+            return;
+        }
+        if let Some(error) = &monitor.error {
+            ui.text_colored([1.0, 0.3, 0.3, 1.0], error);
+        }
+        let now = Utc::now();
+        ui.columns(5, "LoginSessions", true);
+        for header in ["User", "TTY", "From", "Login", "Idle"] {
+            ui.text(header);
+            ui.next_column();
+        }
+        ui.separator();
+        for session in &monitor.sessions {
+            ui.text(&session.user);
+            ui.next_column();
+            ui.text(&session.line);
+            ui.next_column();
+            ui.text(or_dash(&session.host));
+            ui.next_column();
+            ui.text(format!(
+                "{} ({})",
+                format_time(session.login_time),
+                format_span(now - session.login_time)
+            ));
+            ui.next_column();
+            match session.idle {
+                Some(idle) => ui.text(format_span(idle)),
+                None => ui.text_disabled("?"),
+            }
+            ui.next_column();
+        }
+        ui.columns(1, "", false);
+
+        if let Some(_node) = ui.tree_node(format!("Recent logins ({})", monitor.history.len())) {
+            ui.columns(5, "LoginHistory", true);
+            for header in ["User", "TTY", "From", "Login", "Logout"] {
+                ui.text(header);
+                ui.next_column();
+            }
+            ui.separator();
+            for record in &monitor.history {
+                if record.reboot {
+                    ui.text_disabled(&record.user);
+                } else {
+                    ui.text(&record.user);
+                }
+                ui.next_column();
+                ui.text(&record.line);
+                ui.next_column();
+                ui.text(or_dash(&record.host));
+                ui.next_column();
+                ui.text(format_time(record.login_time));
+                ui.next_column();
+                let duration = record
+                    .duration()
+                    .map(|duration| format!(" ({})", format_span(duration)))
+                    .unwrap_or_default();
+                match &record.end {
+                    LoginEnd::StillLoggedIn if record.reboot => ui.text("still running"),
+                    LoginEnd::StillLoggedIn => ui.text("still logged in"),
+                    LoginEnd::Logout(time) => {
+                        ui.text(format!("{}{}", format_time(*time), duration))
+                    }
+                    LoginEnd::Reboot(_) if record.reboot => ui.text(format!("down{}", duration)),
+                    LoginEnd::Reboot(_) => ui.text(format!("crash{}", duration)),
+                }
+                ui.next_column();
+            }
+            ui.columns(1, "", false);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Enregistrement glibc construit octet par octet, comme dans un fichier utmp
+        fn record(
+            kind: i16,
+            line: &str,
+            user: &str,
+            host: &str,
+            time: i32,
+            address: [i32; 4],
+        ) -> Vec<u8> {
+            let mut bytes = vec![0u8; UTMP_RECORD_SIZE];
+            bytes[0..2].copy_from_slice(&kind.to_ne_bytes());
+            bytes[4..8].copy_from_slice(&1234i32.to_ne_bytes());
+            bytes[8..8 + line.len()].copy_from_slice(line.as_bytes());
+            bytes[44..44 + user.len()].copy_from_slice(user.as_bytes());
+            bytes[76..76 + host.len()].copy_from_slice(host.as_bytes());
+            bytes[340..344].copy_from_slice(&time.to_ne_bytes());
+            bytes[344..348].copy_from_slice(&250_000i32.to_ne_bytes());
+            for (index, word) in address.iter().enumerate() {
+                bytes[348 + index * 4..352 + index * 4].copy_from_slice(&word.to_ne_bytes());
+            }
+            bytes
+        }
+
+        fn at(seconds: i64) -> DateTime<Utc> {
+            // record() écrit tv_usec = 250 000
+            Utc.timestamp_opt(seconds, 250_000_000).unwrap()
+        }
+
+        #[test]
+        fn parses_glibc_record() {
+            let ipv4 = i32::from_ne_bytes([192, 168, 1, 20]);
+            let bytes = record(
+                7,
+                "pts/0",
+                "alice",
+                "192.168.1.20",
+                1_700_000_000,
+                [ipv4, 0, 0, 0],
+            );
+            let parsed = UtmpRecord::parse(&bytes).unwrap();
+            assert_eq!(parsed.kind, UtmpKind::UserProcess);
+            assert_eq!(parsed.pid, 1234);
+            assert_eq!(parsed.line, "pts/0");
+            assert_eq!(parsed.user, "alice");
+            assert_eq!(parsed.host, "192.168.1.20");
+            assert_eq!(
+                parsed.time,
+                Utc.timestamp_opt(1_700_000_000, 250_000_000).unwrap()
+            );
+            assert_eq!(parsed.address, Some("192.168.1.20".parse().unwrap()));
+
+            let ipv6: Vec<i32> = "2001:db8::1"
+                .parse::<Ipv6Addr>()
+                .unwrap()
+                .octets()
+                .chunks(4)
+                .map(|word| i32::from_ne_bytes(word.try_into().unwrap()))
+                .collect();
+            let bytes = record(7, "pts/1", "bob", "", 0, ipv6.try_into().unwrap());
+            let parsed = UtmpRecord::parse(&bytes).unwrap();
+            assert_eq!(parsed.address, Some("2001:db8::1".parse().unwrap()));
+
+            assert!(UtmpRecord::parse(&bytes[..UTMP_RECORD_SIZE - 1]).is_none());
+            assert!(UtmpRecord::parse(&record(42, "", "", "", 0, [0; 4])).is_none());
+        }
+
+        #[test]
+        fn reads_last_records_of_file() {
+            let path =
+                std::env::temp_dir().join(format!("system_monitor-wtmp-{}", std::process::id()));
+            let content: Vec<u8> = (0..5)
+                .flat_map(|index| record(7, "tty1", &format!("user{}", index), "", index, [0; 4]))
+                .collect();
+            fs::write(&path, content).unwrap();
+            let all = read_utmp(&path, None).unwrap();
+            let last = read_utmp(&path, Some(2)).unwrap();
+            fs::remove_file(&path).unwrap();
+            assert_eq!(all.len(), 5);
+            let users: Vec<&str> = last.iter().map(|record| record.user.as_str()).collect();
+            assert_eq!(users, ["user3", "user4"]);
+        }
+
+        #[test]
+        fn pairs_logins_with_logouts_and_reboots() {
+            let records: Vec<UtmpRecord> = [
+                record(2, "~", "reboot", "6.1", 1000, [0; 4]),
+                record(7, "pts/1", "carol", "host", 2000, [0; 4]),
+                record(8, "pts/1", "", "", 3000, [0; 4]),
+                record(7, "pts/2", "dave", "", 4000, [0; 4]),
+                record(2, "~", "reboot", "6.2", 9000, [0; 4]),
+                record(7, "tty2", "bob", "", 9500, [0; 4]),
+            ]
+            .iter()
+            .filter_map(|bytes| UtmpRecord::parse(bytes))
+            .collect();
+            let history = login_history(&records, 10);
+            let summary: Vec<(&str, &LoginEnd)> = history
+                .iter()
+                .map(|login| (login.user.as_str(), &login.end))
+                .collect();
+            assert_eq!(
+                summary,
+                [
+                    ("bob", &LoginEnd::StillLoggedIn),
+                    ("reboot", &LoginEnd::StillLoggedIn),
+                    ("dave", &LoginEnd::Reboot(at(9000))),
+                    ("carol", &LoginEnd::Logout(at(3000))),
+                    ("reboot", &LoginEnd::Reboot(at(9000))),
+                ]
+            );
+            assert!(history[1].reboot && !history[0].reboot);
+            assert_eq!(history[3].duration(), Some(chrono::Duration::seconds(1000)));
+            assert_eq!(login_history(&records, 2).len(), 2);
+        }
+    }
+}
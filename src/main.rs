@@ -6,8 +6,10 @@ extern crate sdl2;
 
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Instant, SystemTime};
 
+use chrono::{DateTime, Utc};
+use clap::Parser;
 use graphs::graph;
 use imgui::*;
 use imgui_opengl_renderer::Renderer;
@@ -16,11 +18,40 @@ use sdl2::event::Event;
 use sdl2::keyboard::Keycode;
 use sdl2::video::GLProfile;
 
-use sysinfo::System;
 use system_monitor::*;
 
 #[tokio::main]
 async fn main() {
+    // Les sous-commandes fonctionnent sans SDL ni OpenGL (SSH, conteneurs)
+    let cli = Cli::parse();
+    let (mut config_watcher, mut config) =
+        match ConfigWatcher::load(cli.config.clone(), cli.config_overrides()) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                eprintln!("Invalid option: {}", e);
+                std::process::exit(1);
+            }
+        };
+    if let Some(error) = &config_watcher.error {
+        eprintln!("Configuration error: {}", error);
+    }
+    if let Some(command) = cli.command {
+        run_command(command, &cli.metrics_prefix, &config_watcher, &config).await;
+        return;
+    }
+    if let Some(bind) = cli.metrics_bind {
+        let config = ExporterConfig {
+            bind,
+            prefix: cli.metrics_prefix.clone(),
+            top_processes: 10,
+        };
+        tokio::spawn(async move {
+            if let Err(e) = serve_metrics(config).await {
+                eprintln!("Metrics exporter stopped: {}", e);
+            }
+        });
+    }
+
     // Initialize SDL
     let sdl_context = sdl2::init().unwrap();
     let video_subsystem = sdl_context.video().unwrap();
@@ -32,7 +63,11 @@ async fn main() {
     gl_attr.set_context_version(3, 0);
 
     let window = video_subsystem
-        .window("Dear ImGui SDL2+OpenGL3 example", 1280, 720)
+        .window(
+            "Dear ImGui SDL2+OpenGL3 example",
+            config.layout.width,
+            config.layout.height,
+        )
         .opengl()
         .resizable()
         .allow_highdpi()
@@ -48,7 +83,8 @@ async fn main() {
 
     // Setup Dear ImGui context
     let mut imgui = Context::create();
-    imgui.set_ini_filename(None);
+    // Disposition des fenêtres conservée entre les lancements
+    imgui.set_ini_filename(imgui_ini_path());
 
     let mut platform = ImguiSdl2::new(&mut imgui, &window);
     let renderer = Renderer::new(&mut imgui, |s| {
@@ -58,37 +94,183 @@ async fn main() {
     let clear_color = [0.0, 0.0, 0.0, 1.0];
 
     // Créez des instances de GraphData pour chaque type de graphique
-    let cpu_graph = Arc::new(Mutex::new(graph::GraphData::new(
-        100,
-        Duration::from_secs_f32(1.0),
-    )));
-    let fan_graph = Arc::new(Mutex::new(graph::GraphData::new(
-        100,
-        Duration::from_secs_f32(1.0),
-    )));
-    let temp_graph = Arc::new(Mutex::new(graph::GraphData::new(
-        100,
-        Duration::from_secs_f32(1.0),
-    )));
-
-    let system = Arc::new(Mutex::new(System::new_all()));
-    let mut selected_pids = HashSet::new();
-    let system_clone = system.clone();
-    tokio::spawn(async move {
-        loop {
-            {
-                let mut sys = system_clone.lock().unwrap();
-                sys.refresh_processes();
+    let new_graph = |config: &Config| {
+        let mut graph =
+            graph::GraphData::new(config.sampling.history_length, config.sampling.graph_interval);
+        graph.set_archives(&config.sampling.archives);
+        graph
+    };
+    let cpu_graph = Arc::new(Mutex::new(new_graph(&config)));
+    let fan_graph = Arc::new(Mutex::new(new_graph(&config)));
+    let temp_graph = Arc::new(Mutex::new(new_graph(&config)));
+    // Historique de la répartition mémoire (en Mo)
+    let mut mem_used_graph = new_graph(&config);
+    let mut mem_cache_graph = new_graph(&config);
+    let mut mem_available_graph = new_graph(&config);
+    // Pression mémoire: utilisation RAM/Swap (%) et taux de /proc/vmstat
+    let mut ram_graph = new_graph(&config);
+    let mut swap_graph = new_graph(&config);
+    let mut swap_in_graph = new_graph(&config);
+    let mut swap_out_graph = new_graph(&config);
+    let mut pgfault_graph = new_graph(&config);
+    let mut pgmajfault_graph = new_graph(&config);
+    // Batteries: charge (%) et puissance consommée (W)
+    let mut battery_graph = new_graph(&config);
+    let mut power_draw_graph = new_graph(&config);
+    let mut power_supplies = Vec::new();
+    // Consommation du processeur (RAPL): package, cœurs et mémoire, en W
+    let mut rapl_meter = RaplMeter::new(&config.power.powercap_root);
+    let mut rapl_package_graph = new_graph(&config);
+    let mut rapl_core_graph = new_graph(&config);
+    let mut rapl_dram_graph = new_graph(&config);
+    let mut swap_vmstat = (VmStat::read().unwrap_or_default(), Instant::now());
+    let mut fault_vmstat = (VmStat::read().unwrap_or_default(), Instant::now());
+    // Surveillance des événements du noyau (OOM, segfault, MCE, I/O)
+    let event_watcher = EventWatcher::from_env();
+    // Les messages déjà présents dans le journal au lancement ne sont pas marqués
+    let started_at = SystemTime::now();
+    let mut seen_events = 0;
+
+    // Relecture d'une session enregistrée à la place du système local
+    let mut player = match &cli.replay {
+        Some(path) => match SessionPlayer::open(path) {
+            Ok(player) => Some(player),
+            Err(e) => {
+                eprintln!("Error opening session {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let mut replay_loaded = false;
+
+    // Agents distants et sessions suivies: l'hôte 0 est la machine locale (ou la session relue)
+    let mut remotes: Vec<RemoteHost> = cli
+        .connect
+        .iter()
+        .map(|address| RemoteHost::connect(address))
+        .chain(cli.follow.iter().map(|path| RemoteHost::follow(path)))
+        .collect();
+    let mut host_index = 0;
+    let mut selected_host = 0;
+    let mut fleet_view = FleetView::new();
+    let mut local_sampler: Option<LocalSampler> = None;
+
+    // Historique persistant (lu au démarrage), seulement pour le système local
+    let history_store = match config.history.path() {
+        Some(path) if config.history.enabled && player.is_none() => {
+            match HistoryStore::open(
+                &path,
+                &config.history.retention,
+                config.history.flush_interval,
+            ) {
+                Ok(store) => Some(Arc::new(store)),
+                Err(e) => {
+                    eprintln!("History error: {}", e);
+                    None
+                }
             }
-            tokio::time::sleep(Duration::from_millis(100)).await;
         }
-    });
-    let computer = Computer::new();
+        _ => None,
+    };
+    if let Some(store) = &history_store {
+        for (metric, graph) in [("cpu", &cpu_graph), ("fan", &fan_graph), ("temperature", &temp_graph)] {
+            graph.lock().unwrap().set_history(metric, store.clone());
+        }
+        for (metric, graph) in [
+            ("mem_used", &mut mem_used_graph),
+            ("mem_cache", &mut mem_cache_graph),
+            ("mem_available", &mut mem_available_graph),
+            ("ram", &mut ram_graph),
+            ("swap", &mut swap_graph),
+            ("swap_in", &mut swap_in_graph),
+            ("swap_out", &mut swap_out_graph),
+            ("page_faults", &mut pgfault_graph),
+            ("major_faults", &mut pgmajfault_graph),
+            ("battery", &mut battery_graph),
+            ("power_draw", &mut power_draw_graph),
+            ("cpu_package_power", &mut rapl_package_graph),
+            ("cpu_core_power", &mut rapl_core_graph),
+            ("dram_power", &mut rapl_dram_graph),
+        ] {
+            graph.set_history(metric, store.clone());
+        }
+    }
+
+    // Règles d'alerte: évaluées en continu en direct, à chaque image en relecture
+    let mut alert_monitor = AlertMonitor::load(cli.rules.clone());
+    let mut alert_position = None;
+    if player.is_none() {
+        alert_monitor.start(config.sampling.alert_interval);
+    }
+    for remote in remotes.iter_mut() {
+        remote.set_rules(alert_monitor.rules());
+    }
+
+    let (mut preference_store, mut preferences) = PreferenceStore::load();
+    if let Some(error) = &preference_store.error {
+        eprintln!("Preferences error: {}", error);
+    }
+    for (name, graph) in [("cpu", &cpu_graph), ("fan", &fan_graph), ("temperature", &temp_graph)] {
+        if let Some(settings) = preferences.graphs.get(name) {
+            graph.lock().unwrap().apply_settings(settings);
+        }
+    }
+    for (name, graph) in [
+        ("ram", &mut ram_graph),
+        ("swap", &mut swap_graph),
+        ("page_faults", &mut pgfault_graph),
+        ("power", &mut battery_graph),
+    ] {
+        if let Some(settings) = preferences.graphs.get(name) {
+            graph.apply_settings(settings);
+        }
+    }
+    let mut selected_pids: HashSet<usize> = preferences.selected_pids.iter().copied().collect();
+    let mut process_table = preferences.process_table.clone();
+    let mut graphics_tab = preferences.graphics_tab.clone();
+    let mut restore_tab = preferences.graphics_tab.clone();
+    let computer = player
+        .as_ref()
+        .and_then(|player| player.frames.iter().find_map(|frame| frame.computer.clone()))
+        .unwrap_or_else(Computer::new);
     let mut network = Network::new();
     network.initialize();
-    let mut show_ip = false;
-    let mut show_rx_bar = false;
-    let mut show_tx_bar = false;
+    let mut show_ip = preferences.show_ip;
+    let mut show_rx_bar = preferences.show_rx_bar;
+    let mut show_tx_bar = preferences.show_tx_bar;
+    let snapshot_saver = SnapshotSaver::new();
+    let mut recorder_panel = RecorderPanel::new();
+    // Thème: --theme, sinon le dernier choisi dans l'interface, sinon celui du fichier
+    let mut theme_name = match &cli.theme {
+        Some(_) => config.theme.clone(),
+        None => preferences
+            .theme
+            .clone()
+            .filter(|name| Theme::resolve(name, &config.themes).is_ok())
+            .unwrap_or_else(|| config.theme.clone()),
+    };
+    let mut theme = config.resolve_theme(&theme_name);
+    let mut theme_changed = true;
+    let mut processes = Vec::new();
+    let mut last_process_refresh: Option<Instant> = None;
+    // Hiérarchie cgroup v2, relue à l'intervalle configuré quand la fenêtre est dépliée
+    let mut cgroup_reader = CgroupReader::new(&config.cgroups.root, &config.cgroups.proc_root);
+    let mut cgroup_tree: Option<Result<CgroupNode, String>> = None;
+    let mut last_cgroup_refresh: Option<Instant> = None;
+    let mut cgroup_view = CgroupView::new();
+    // Processus groupés par unité systemd, état des unités de la machine locale
+    let mut service_monitor =
+        ServiceMonitor::new(&config.services.systemctl, &config.cgroups.root);
+    let mut service_view = ServiceView::new();
+    // Sessions ouvertes et dernières connexions (utmp/wtmp) de la machine locale
+    let mut login_monitor = LoginMonitor::new(
+        &config.logins.utmp,
+        &config.logins.wtmp,
+        config.logins.history,
+    );
+    // Après un rechargement de la configuration, les fenêtres reprennent la disposition du fichier
+    let mut layout_condition = Condition::FirstUseEver;
 
     let mut event_pump = sdl_context.event_pump().unwrap();
     'running: loop {
@@ -108,171 +290,824 @@ async fn main() {
             }
         }
 
+        if let Some(new_config) = config_watcher.poll() {
+            if new_config.theme != config.theme {
+                theme_name = new_config.theme.clone();
+            }
+            theme = Theme::resolve(&theme_name, &new_config.themes).unwrap_or_else(|_| {
+                theme_name = new_config.theme.clone();
+                new_config.resolve_theme(&theme_name)
+            });
+            theme_changed = true;
+            for graph in [&cpu_graph, &fan_graph, &temp_graph] {
+                let mut graph = graph.lock().unwrap();
+                graph.configure(
+                    new_config.sampling.graph_interval,
+                    new_config.sampling.history_length,
+                );
+                graph.set_archives(&new_config.sampling.archives);
+            }
+            for graph in [
+                &mut mem_used_graph,
+                &mut mem_cache_graph,
+                &mut mem_available_graph,
+                &mut ram_graph,
+                &mut swap_graph,
+                &mut swap_in_graph,
+                &mut swap_out_graph,
+                &mut pgfault_graph,
+                &mut pgmajfault_graph,
+                &mut battery_graph,
+                &mut power_draw_graph,
+                &mut rapl_package_graph,
+                &mut rapl_core_graph,
+                &mut rapl_dram_graph,
+            ] {
+                graph.configure(
+                    new_config.sampling.graph_interval,
+                    new_config.sampling.history_length,
+                );
+                graph.set_archives(&new_config.sampling.archives);
+            }
+            if new_config.layout != config.layout {
+                layout_condition = Condition::Always;
+            }
+            replay_loaded = false;
+            config = new_config;
+        }
+        if theme_changed {
+            theme.apply(imgui.style_mut());
+            for graph in [&cpu_graph, &fan_graph, &temp_graph] {
+                graph.lock().unwrap().marker_color = theme.marker.0;
+            }
+            for graph in [
+                &mut mem_used_graph,
+                &mut mem_cache_graph,
+                &mut mem_available_graph,
+                &mut ram_graph,
+                &mut swap_graph,
+                &mut swap_in_graph,
+                &mut swap_out_graph,
+                &mut pgfault_graph,
+                &mut pgmajfault_graph,
+                &mut battery_graph,
+                &mut power_draw_graph,
+                &mut rapl_package_graph,
+                &mut rapl_core_graph,
+                &mut rapl_dram_graph,
+            ] {
+                graph.marker_color = theme.marker.0;
+            }
+            theme_changed = false;
+        }
+
         platform.prepare_frame(imgui.io_mut(), &window, &event_pump.mouse_state());
         let ui = imgui.frame();
 
-        ui.window("== Memory and Processes ==")
-            .size([620.0, 370.0], Condition::FirstUseEver)
-            .position([650.0, 10.0], Condition::FirstUseEver)
-            .build(|| {
-                // Code pour la fenêtre Mémoire et processus
-                let mut memory = Memory::new();
-                // Affichage des informations dans la fenêtre
-                memory.get_memory();
-                ui.text("Memory Information:");
-                ui.text(format!(
-                    "Total RAM: {}",
-                    convert_bytes_to_any(memory.ram.total_ram)
-                ));
-                let free_memory = (memory.ram.used_ram) as f32 / memory.ram.total_ram as f32;
-                ProgressBar::new(free_memory)
-                    .size([300.0, 24.0])
-                    .overlay_text(format!(
-                        "Free RAM: {}",
-                        convert_bytes_to_any(memory.ram.free_ram)
-                    ))
-                    .build(&ui);
-                ui.text(format!(
-                    "Total Swap: {}",
-                    convert_bytes_to_any(memory.swap.total_swap)
-                ));
-                let free_swap = (memory.swap.used_swap) as f32 / memory.swap.total_swap as f32;
-                ProgressBar::new(free_swap)
-                    .size([300.0, 24.0])
-                    .overlay_text(format!(
-                        "Free Swap: {}",
-                        convert_bytes_to_any(memory.swap.free_swap)
-                    ))
-                    .build(&ui);
-                ui.text(format!(
-                    "Total Storage: {}",
-                    convert_bytes_to_any(memory.storage.total_disk)
-                ));
-                let free_storage =
-                    (memory.storage.used_disk) as f32 / memory.storage.total_disk as f32;
-                ProgressBar::new(free_storage)
-                    .size([300.0, 24.0])
-                    .overlay_text(format!(
-                        "Free Storage: {}",
-                        convert_bytes_to_any(memory.storage.free_disk)
-                    ))
-                    .build(&ui);
-                ui.text("\n");
-                ui.separator();
-                // Table des Processuses
-                ui.text("\n");
-                let binding = system.clone();
-                let mut system = binding.lock().unwrap();
-                draw_process_table(ui, &mut system, &mut selected_pids);
-            });
+        for (index, remote) in remotes.iter_mut().enumerate() {
+            if remote.sync() && host_index == index + 1 {
+                replay_loaded = false;
+            }
+        }
+        if selected_host != host_index {
+            host_index = selected_host;
+            replay_loaded = false;
+            // De retour sur la machine locale, les graphiques repartent de zéro
+            if host_index == 0 && player.is_none() {
+                for graph in [&cpu_graph, &fan_graph, &temp_graph] {
+                    graph.lock().unwrap().set_samples(Vec::new());
+                }
+                for graph in [
+                    &mut mem_used_graph,
+                    &mut mem_cache_graph,
+                    &mut mem_available_graph,
+                    &mut ram_graph,
+                    &mut swap_graph,
+                    &mut swap_in_graph,
+                    &mut swap_out_graph,
+                    &mut pgfault_graph,
+                    &mut pgmajfault_graph,
+                    &mut battery_graph,
+                    &mut power_draw_graph,
+                    &mut rapl_package_graph,
+                    &mut rapl_core_graph,
+                    &mut rapl_dram_graph,
+                ] {
+                    graph.set_samples(Vec::new());
+                }
+            }
+        }
+        let host_labels: Vec<String> = std::iter::once(match &player {
+            Some(player) => player.title.clone(),
+            None => "Local".to_string(),
+        })
+        .chain(remotes.iter().map(RemoteHost::label))
+        .collect();
 
-        ui.window("== System ==")
-            .size([600.0, 370.0], Condition::FirstUseEver)
-            .position([10.0, 10.0], Condition::FirstUseEver)
-            .build(|| {
-                // Code pour la fenêtre Système
-                computer.display(ui);
-                ui.window("Graphics")
-                    .size([600.0, 240.0], Condition::FirstUseEver)
-                    .position([10.0, 140.0], Condition::FirstUseEver)
-                    .build(|| {
-                        // Appeler adjust_intervals chaque fois que le FPS est modifié
-                        adjust_intervals(cpu_graph.clone(), fan_graph.clone(), temp_graph.clone());
-
-                        if let Some(tab_bar) = ui.tab_bar("Performance Tabs") {
-                            if let Some(tab) = ui.tab_item("CPU") {
-                                let binding = cpu_graph.clone();
-                                let mut cpu_graph = binding.lock().unwrap();
-                                if !cpu_graph.is_paused && cpu_graph.last_update.elapsed() >= cpu_graph.update_interval {
-                                    let cpu_usage = Cpu::get_cpu_usage();
-                                    cpu_graph.last_update = Instant::now();
-                                    {
-                                        cpu_graph.update(cpu_usage);
-                                    }
+        // En relecture ou pour un agent, les graphiques sont reconstruits à partir des snapshots
+        let live = active_player(&mut player, &mut remotes, host_index).is_none();
+        if let Some(player) = active_player(&mut player, &mut remotes, host_index) {
+            if player.advance() || !replay_loaded {
+                replay_loaded = true;
+                load_replay_graphs(
+                    player,
+                    &mut [
+                        (&mut cpu_graph.lock().unwrap(), ReplayValue::CpuUsage),
+                        (&mut fan_graph.lock().unwrap(), ReplayValue::FanSpeed),
+                        (
+                            &mut temp_graph.lock().unwrap(),
+                            ReplayValue::Temperature(config.units.temperature),
+                        ),
+                        (&mut mem_used_graph, ReplayValue::MemUsed),
+                        (&mut mem_cache_graph, ReplayValue::MemCache),
+                        (&mut mem_available_graph, ReplayValue::MemAvailable),
+                        (&mut ram_graph, ReplayValue::RamPercent),
+                        (&mut swap_graph, ReplayValue::SwapPercent),
+                        (&mut swap_in_graph, ReplayValue::SwapIn),
+                        (&mut swap_out_graph, ReplayValue::SwapOut),
+                        (&mut pgfault_graph, ReplayValue::PageFaults),
+                        (&mut pgmajfault_graph, ReplayValue::MajorFaults),
+                    ],
+                );
+            }
+        }
+        let replay_frame = active_player(&mut player, &mut remotes, host_index)
+            .map(|player| player.current().clone());
+        // Les alertes portent sur la machine locale ou la session relue
+        if let Some(player) = player.as_ref().filter(|_| host_index == 0) {
+            if alert_position != Some(player.position) {
+                if alert_position.is_some_and(|position| position > player.position) {
+                    alert_monitor.reset();
+                }
+                alert_position = Some(player.position);
+                alert_monitor.evaluate(player.current());
+            }
+        }
+
+        // Marquer les nouveaux événements sur les graphiques CPU et mémoire (noyau local),
+        // à l'heure de l'événement
+        let received = event_watcher.received();
+        if received > seen_events {
+            if host_index == 0 {
+                for timestamp in event_watcher.latest_timestamps(received - seen_events) {
+                    if timestamp < started_at {
+                        continue;
+                    }
+                    let timestamp = DateTime::<Utc>::from(timestamp);
+                    cpu_graph.lock().unwrap().mark_at(timestamp);
+                    ram_graph.mark_at(timestamp);
+                    mem_used_graph.mark_at(timestamp);
+                }
+            }
+            seen_events = received;
+        }
+
+        // Alimentations de la machine locale, relues à l'intervalle du graphique de charge
+        if live && battery_graph.last_update.elapsed() >= battery_graph.update_interval {
+            power_supplies = read_power_supplies(&config.power.supply_root);
+            battery_graph.last_update = Instant::now();
+            if let Some(totals) = BatteryTotals::from_supplies(&power_supplies) {
+                battery_graph.update(totals.charge);
+                if let Some(power) = totals.power {
+                    if !battery_graph.is_paused {
+                        power_draw_graph.update(power as f32);
+                    }
+                }
+            }
+        }
+
+        // Processus partagés par les fenêtres mémoire, cgroups et services, relus avant
+        // de les construire pour ne pas dépendre de l'état de la fenêtre mémoire
+        match replay_frame.as_ref() {
+            Some(frame) => processes = frame.processes.clone().unwrap_or_default(),
+            None => {
+                // La liste des processus est coûteuse: rafraîchie à l'intervalle configuré
+                if last_process_refresh
+                    .is_none_or(|last| last.elapsed() >= config.sampling.process_refresh)
+                {
+                    processes = get_process_info_with_cgroups(&config.cgroups.proc_root);
+                    last_process_refresh = Some(Instant::now());
+                }
+            }
+        }
+
+        if config.panels.memory {
+            ui.window("== Memory and Processes ==")
+                .size(config.layout.memory.size, layout_condition)
+                .position(config.layout.memory.position, layout_condition)
+                .build(|| {
+                    // Code pour la fenêtre Mémoire et processus
+                    let memory = match replay_frame.as_ref() {
+                        Some(frame) => frame.memory.clone().unwrap_or_else(Memory::new),
+                        None => {
+                            let mut memory = Memory::new();
+                            memory.get_memory();
+                            memory
+                        }
+                    };
+                    // Affichage des informations dans la fenêtre
+                    ui.text("Memory Information:");
+                    ui.text(format!(
+                        "Total RAM: {}",
+                        convert_bytes_to_any(memory.ram.total_ram)
+                    ));
+                    let used_memory = (memory.ram.used_ram) as f32 / memory.ram.total_ram as f32;
+                    ProgressBar::new(used_memory)
+                        .size([300.0, 24.0])
+                        .overlay_text(format!(
+                            "Used RAM: {} (Free: {})",
+                            convert_bytes_to_any(memory.ram.used_ram),
+                            convert_bytes_to_any(memory.ram.free_ram)
+                        ))
+                        .build(&ui);
+                    let meminfo = match replay_frame.as_ref() {
+                        Some(frame) => frame.meminfo.clone().unwrap_or_default(),
+                        None => MemInfo::read().unwrap_or_default(),
+                    };
+                    draw_memory_breakdown(ui, &meminfo);
+                    if live && mem_used_graph.last_update.elapsed() >= mem_used_graph.update_interval
+                    {
+                        const MO: f32 = 1024.0 * 1024.0;
+                        mem_used_graph.last_update = Instant::now();
+                        mem_used_graph.update(meminfo.used() as f32 / MO);
+                        mem_cache_graph.update(meminfo.cache() as f32 / MO);
+                        mem_available_graph.update(meminfo.mem_available as f32 / MO);
+                    }
+                    if ui.collapsing_header("Memory History", TreeNodeFlags::empty()) {
+                        mem_used_graph.draw_range_selector(ui);
+                        mem_cache_graph.set_view(mem_used_graph.view);
+                        mem_available_graph.set_view(mem_used_graph.view);
+                        Plot::new("Memory")
+                            .unit(" Mo")
+                            .series("Used", &mem_used_graph, theme.graph_line(0))
+                            .series("Cache", &mem_cache_graph, theme.graph_line(1))
+                            .series("Available", &mem_available_graph, theme.graph_line(2))
+                            .draw(ui, &mut mem_used_graph.plot_view);
+                    }
+                    ui.text(format!(
+                        "Total Swap: {}",
+                        convert_bytes_to_any(memory.swap.total_swap)
+                    ));
+                    let free_swap = (memory.swap.used_swap) as f32 / memory.swap.total_swap as f32;
+                    ProgressBar::new(free_swap)
+                        .size([300.0, 24.0])
+                        .overlay_text(format!(
+                            "Free Swap: {}",
+                            convert_bytes_to_any(memory.swap.free_swap)
+                        ))
+                        .build(&ui);
+                    ui.text(format!(
+                        "Total Storage: {}",
+                        convert_bytes_to_any(memory.storage.total_disk)
+                    ));
+                    let free_storage =
+                        (memory.storage.used_disk) as f32 / memory.storage.total_disk as f32;
+                    ProgressBar::new(free_storage)
+                        .size([300.0, 24.0])
+                        .overlay_text(format!(
+                            "Free Storage: {}",
+                            convert_bytes_to_any(memory.storage.free_disk)
+                        ))
+                        .build(&ui);
+                    ui.text("\n");
+                    ui.separator();
+                    // Table des Processuses
+                    ui.text("\n");
+                    draw_process_table(ui, &processes, &mut selected_pids, &mut process_table);
+                });
+        }
+
+        if config.panels.system {
+            ui.window("== System ==")
+                .size(config.layout.system.size, layout_condition)
+                .position(config.layout.system.position, layout_condition)
+                .build(|| {
+                    // Code pour la fenêtre Système
+                    if host_labels.len() > 1 {
+                        ui.combo_simple_string("Host", &mut selected_host, &host_labels);
+                    }
+                    replay_frame
+                        .as_ref()
+                        .and_then(|frame| frame.computer.as_ref())
+                        .unwrap_or(&computer)
+                        .display(ui);
+                    if live {
+                        draw_power_summary(ui, &power_supplies);
+                    }
+                    let theme_list = theme_names(&config.themes);
+                    let mut theme_index =
+                        theme_list.iter().position(|name| *name == theme_name).unwrap_or(0);
+                    if ui.combo_simple_string("Theme", &mut theme_index, &theme_list) {
+                        theme_name = theme_list[theme_index].clone();
+                        theme = config.resolve_theme(&theme_name);
+                        theme_changed = true;
+                    }
+                    if let Some(error) = &config_watcher.error {
+                        ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("Config error: {}", error));
+                    }
+                    if let Some(store) = &history_store {
+                        let status = store.status.lock().unwrap().clone();
+                        match &status.error {
+                            Some(error) => ui.text_colored(
+                                [1.0, 0.3, 0.3, 1.0],
+                                format!("History error: {}", error),
+                            ),
+                            None => ui.text(format!(
+                                "History: {} samples saved to {}",
+                                status.written,
+                                store.path.display()
+                            )),
+                        }
+                    }
+                    // En relecture ou pour un hôte distant, le snapshot affiché est enregistré
+                    if ui.button("Save snapshot") {
+                        snapshot_saver.save(replay_frame.clone());
+                    }
+                    let snapshot_status = snapshot_saver.status();
+                    if !snapshot_status.is_empty() {
+                        ui.same_line();
+                        ui.text(&snapshot_status);
+                    }
+                    if live {
+                        login_monitor.utmp = config.logins.utmp.clone();
+                        login_monitor.wtmp = config.logins.wtmp.clone();
+                        login_monitor.history_length = config.logins.history;
+                        if login_monitor
+                            .last_refresh
+                            .is_none_or(|last| last.elapsed() >= config.logins.refresh)
+                        {
+                            login_monitor.refresh();
+                        }
+                        draw_logins(ui, &login_monitor);
+                    }
+                });
+        }
+
+        if config.panels.graphics {
+            ui.window("Graphics")
+                .size(config.layout.graphics.size, layout_condition)
+                .position(config.layout.graphics.position, layout_condition)
+                .build(|| {
+                    // Appeler adjust_intervals chaque fois que le FPS est modifié
+                    adjust_intervals(cpu_graph.clone(), fan_graph.clone(), temp_graph.clone());
+                    ram_graph.adjust_interval();
+                    swap_graph.adjust_interval();
+                    pgfault_graph.adjust_interval();
+                    battery_graph.adjust_interval();
+
+                    // L'historique mémoire est échantillonné même quand l'onglet est masqué
+                    if live && ram_graph.last_update.elapsed() >= ram_graph.update_interval {
+                        let meminfo = MemInfo::read().unwrap_or_default();
+                        ram_graph.last_update = Instant::now();
+                        ram_graph.update(meminfo.used_percent());
+                    }
+                    if live && swap_graph.last_update.elapsed() >= swap_graph.update_interval {
+                        let meminfo = MemInfo::read().unwrap_or_default();
+                        let vmstat = VmStat::read().unwrap_or_default();
+                        let rates = vmstat.rates_since(&swap_vmstat.0, swap_vmstat.1.elapsed());
+                        swap_vmstat = (vmstat, Instant::now());
+                        swap_graph.last_update = Instant::now();
+                        swap_graph.update(meminfo.swap_used_percent());
+                        if !swap_graph.is_paused {
+                            swap_in_graph.update(rates.swap_in);
+                            swap_out_graph.update(rates.swap_out);
+                        }
+                    }
+                    if live
+                        && pgfault_graph.last_update.elapsed() >= pgfault_graph.update_interval
+                    {
+                        let vmstat = VmStat::read().unwrap_or_default();
+                        let rates = vmstat.rates_since(&fault_vmstat.0, fault_vmstat.1.elapsed());
+                        fault_vmstat = (vmstat, Instant::now());
+                        pgfault_graph.last_update = Instant::now();
+                        pgfault_graph.update(rates.pgfault);
+                        if !pgfault_graph.is_paused {
+                            pgmajfault_graph.update(rates.pgmajfault);
+                        }
+                    }
+
+                    // Le CPU, les ventilateurs et la température aussi, pour l'historique
+                    if live {
+                        let mut cpu_graph = cpu_graph.lock().unwrap();
+                        if !cpu_graph.is_paused
+                            && cpu_graph.last_update.elapsed() >= cpu_graph.update_interval
+                        {
+                            let cpu_usage = Cpu::get_cpu_usage();
+                            cpu_graph.last_update = Instant::now();
+                            cpu_graph.update(cpu_usage);
+                            rapl_meter.sample();
+                            for (graph, kind) in [
+                                (&mut rapl_package_graph, RaplKind::Package),
+                                (&mut rapl_core_graph, RaplKind::Core),
+                                (&mut rapl_dram_graph, RaplKind::Dram),
+                            ] {
+                                if let Some(watts) = rapl_meter.watts(kind) {
+                                    graph.update(watts as f32);
                                 }
-                                let hover = format!("CPU Usage: #%");
-                                ui.checkbox("Pause Animation", &mut cpu_graph.is_paused);
-                                ui.slider("FPS", 1.0, 60.0, &mut cpu_graph.fps);
-                                ui.slider("Y Scale", 1.0, 10.0, &mut cpu_graph.y_scale);
-                                cpu_graph.draw_graph(&ui, "CPU Usage", &hover);
-                                tab.end();
                             }
+                        }
+                        let mut fan_graph = fan_graph.lock().unwrap();
+                        if !fan_graph.is_paused
+                            && fan_graph.last_update.elapsed() >= fan_graph.update_interval
+                        {
+                            let rpm = Cpu::get_all_fan_info()
+                                .ok()
+                                .and_then(|fans| fans.first().and_then(|fan| fan.rpm))
+                                .unwrap_or(0);
+                            fan_graph.last_update = Instant::now();
+                            fan_graph.update(rpm as f32);
+                        }
+                        let mut temp_graph = temp_graph.lock().unwrap();
+                        if !temp_graph.is_paused
+                            && temp_graph.last_update.elapsed() >= temp_graph.update_interval
+                        {
+                            let cpu_temperature = Cpu::get_cpu_temperatures();
+                            temp_graph.last_update = Instant::now();
+                            temp_graph.update(config.units.temperature.convert(cpu_temperature));
+                        }
+                    }
 
-                            if let Some(tab) = ui.tab_item("Fan") {
-                                let hover: &str = "RPM: #";
-                                let binding = fan_graph.clone();
-                                let mut fan_graph = binding.lock().unwrap();
-                                if !fan_graph.is_paused
-                                    && fan_graph.last_update.elapsed() >= fan_graph.update_interval
-                                {
-                                    let fan_info_list = Cpu::get_all_fan_info().unwrap();
-                                    fan_graph.last_update = Instant::now();
+                    // Au premier affichage, l'onglet de la session précédente est rouvert
+                    let tab_flags = |name: &str| {
+                        if restore_tab.as_deref() == Some(name) {
+                            TabItemFlags::SET_SELECTED
+                        } else {
+                            TabItemFlags::empty()
+                        }
+                    };
+                    if let Some(tab_bar) = ui.tab_bar("Performance Tabs") {
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("CPU", None, tab_flags("CPU"))
+                        {
+                            graphics_tab = Some("CPU".to_string());
+                            let binding = cpu_graph.clone();
+                            let mut cpu_graph = binding.lock().unwrap();
+                            ui.checkbox("Pause Animation", &mut cpu_graph.is_paused);
+                            ui.slider("FPS", 1.0, 60.0, &mut cpu_graph.fps);
+                            ui.slider("Y Scale", 1.0, 10.0, &mut cpu_graph.y_scale);
+                            cpu_graph.draw_range_selector(ui);
+                            Plot::new("CPU Usage")
+                                .unit("%")
+                                .range(PlotRange::Fixed { min: 0.0, max: 100.0 })
+                                .series("CPU", &cpu_graph, theme.graph_line(0))
+                                .draw(ui, &mut cpu_graph.plot_view);
+                            if live {
+                                draw_rapl(ui, &rapl_meter);
+                                if rapl_meter.error.is_none() {
+                                    for graph in [
+                                        &mut rapl_package_graph,
+                                        &mut rapl_core_graph,
+                                        &mut rapl_dram_graph,
+                                    ] {
+                                        graph.set_view(cpu_graph.view);
+                                    }
+                                    let mut plot = Plot::new("CPU Power")
+                                        .unit(" W")
+                                        .y_scale(cpu_graph.y_scale);
+                                    for (index, (graph, kind)) in [
+                                        (&rapl_package_graph, RaplKind::Package),
+                                        (&rapl_core_graph, RaplKind::Core),
+                                        (&rapl_dram_graph, RaplKind::Dram),
+                                    ]
+                                    .into_iter()
+                                    .enumerate()
                                     {
-                                        fan_graph.update(fan_info_list[0].rpm.unwrap_or(0) as f32);
+                                        if rapl_meter.watts(kind).is_some() {
+                                            plot = plot.series(kind.label(), graph, theme.graph_line(index));
+                                        }
                                     }
+                                    plot.draw(ui, &mut rapl_package_graph.plot_view);
                                 }
-
-                                ui.checkbox("Pause Animation", &mut fan_graph.is_paused);
-                                ui.slider("FPS", 1.0, 60.0, &mut fan_graph.fps);
-                                ui.slider("Y Scale", 1.0, 10.0, &mut fan_graph.y_scale);
-                                fan_graph.draw_graph(&ui, "Fan Speed", &hover);
-                                tab.end();
                             }
+                            tab.end();
+                        }
 
-                            if let Some(tab) = ui.tab_item("Thermal") {
-                                let binding = temp_graph.clone();
-                                let mut temp_graph = binding.lock().unwrap();
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("Fan", None, tab_flags("Fan"))
+                        {
+                            graphics_tab = Some("Fan".to_string());
+                            let binding = fan_graph.clone();
+                            let mut fan_graph = binding.lock().unwrap();
+                            ui.checkbox("Pause Animation", &mut fan_graph.is_paused);
+                            ui.slider("FPS", 1.0, 60.0, &mut fan_graph.fps);
+                            ui.slider("Y Scale", 1.0, 10.0, &mut fan_graph.y_scale);
+                            fan_graph.draw_range_selector(ui);
+                            Plot::new("Fan Speed")
+                                .unit(" RPM")
+                                .y_scale(fan_graph.y_scale)
+                                .series("Fan", &fan_graph, theme.graph_line(0))
+                                .draw(ui, &mut fan_graph.plot_view);
+                            tab.end();
+                        }
 
-                                if !temp_graph.is_paused
-                                    && temp_graph.last_update.elapsed()
-                                        >= temp_graph.update_interval
-                                {
-                                    let cpu_temperature = Cpu::get_cpu_temperatures();
-                                    temp_graph.last_update = Instant::now();
-                                    {
-                                        temp_graph.update(cpu_temperature);
-                                    }
-                                }
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("Thermal", None, tab_flags("Thermal"))
+                        {
+                            graphics_tab = Some("Thermal".to_string());
+                            let binding = temp_graph.clone();
+                            let mut temp_graph = binding.lock().unwrap();
+                            ui.checkbox("Pause Animation", &mut temp_graph.is_paused);
+                            ui.slider("FPS", 1.0, 60.0, &mut temp_graph.fps);
+                            ui.slider("Y Scale", 1.0, 10.0, &mut temp_graph.y_scale);
+                            temp_graph.draw_range_selector(ui);
+                            Plot::new("Temperature")
+                                .unit(config.units.temperature.symbol())
+                                .y_scale(temp_graph.y_scale)
+                                .series("CPU", &temp_graph, theme.graph_line(0))
+                                .draw(ui, &mut temp_graph.plot_view);
+                            tab.end();
+                        }
+
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("RAM", None, tab_flags("RAM"))
+                        {
+                            graphics_tab = Some("RAM".to_string());
+                            ui.checkbox("Pause Animation", &mut ram_graph.is_paused);
+                            ui.slider("FPS", 1.0, 60.0, &mut ram_graph.fps);
+                            ram_graph.draw_range_selector(ui);
+                            Plot::new("RAM Usage")
+                                .unit("%")
+                                .range(PlotRange::Fixed { min: 0.0, max: 100.0 })
+                                .series("RAM", &ram_graph, theme.graph_line(0))
+                                .draw(ui, &mut ram_graph.plot_view);
+                            tab.end();
+                        }
+
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("Swap", None, tab_flags("Swap"))
+                        {
+                            graphics_tab = Some("Swap".to_string());
+                            ui.checkbox("Pause Animation", &mut swap_graph.is_paused);
+                            ui.slider("FPS", 1.0, 60.0, &mut swap_graph.fps);
+                            ui.slider("Y Scale", 1.0, 10.0, &mut swap_graph.y_scale);
+                            swap_graph.draw_range_selector(ui);
+                            swap_in_graph.set_view(swap_graph.view);
+                            swap_out_graph.set_view(swap_graph.view);
+                            Plot::new("Swap Usage")
+                                .unit("%")
+                                .range(PlotRange::Fixed { min: 0.0, max: 100.0 })
+                                .series("Swap", &swap_graph, theme.graph_line(0))
+                                .draw(ui, &mut swap_graph.plot_view);
+                            Plot::new("Swap Activity")
+                                .unit(" pages/s")
+                                .y_scale(swap_graph.y_scale)
+                                .series("In", &swap_in_graph, theme.graph_line(0))
+                                .series("Out", &swap_out_graph, theme.graph_line(1))
+                                .draw(ui, &mut swap_in_graph.plot_view);
+                            tab.end();
+                        }
 
-                                let hover = format!("CPU Temperature: #°C",);
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("Page Faults", None, tab_flags("Page Faults"))
+                        {
+                            graphics_tab = Some("Page Faults".to_string());
+                            ui.checkbox("Pause Animation", &mut pgfault_graph.is_paused);
+                            ui.slider("FPS", 1.0, 60.0, &mut pgfault_graph.fps);
+                            ui.slider("Y Scale", 1.0, 10.0, &mut pgfault_graph.y_scale);
+                            pgfault_graph.draw_range_selector(ui);
+                            pgmajfault_graph.set_view(pgfault_graph.view);
+                            Plot::new("Page Faults")
+                                .unit("/s")
+                                .y_scale(pgfault_graph.y_scale)
+                                .series("All", &pgfault_graph, theme.graph_line(0))
+                                .series("Major", &pgmajfault_graph, theme.graph_line(1))
+                                .draw(ui, &mut pgfault_graph.plot_view);
+                            tab.end();
+                        }
 
-                                ui.checkbox("Pause Animation", &mut temp_graph.is_paused);
-                                ui.slider("FPS", 1.0, 60.0, &mut temp_graph.fps);
-                                ui.slider("Y Scale", 1.0, 10.0, &mut temp_graph.y_scale);
-                                temp_graph.draw_graph(&ui, "Temperature", &hover);
-                                tab.end();
+                        if let Some(tab) =
+                            ui.tab_item_with_flags("Power", None, tab_flags("Power"))
+                        {
+                            graphics_tab = Some("Power".to_string());
+                            if live {
+                                draw_power_supplies(ui, &config.power.supply_root, &power_supplies);
+                                ui.checkbox("Pause Animation", &mut battery_graph.is_paused);
+                                ui.slider("FPS", 1.0, 60.0, &mut battery_graph.fps);
+                                ui.slider("Y Scale", 1.0, 10.0, &mut battery_graph.y_scale);
+                                battery_graph.draw_range_selector(ui);
+                                power_draw_graph.set_view(battery_graph.view);
+                                Plot::new("Battery Charge")
+                                    .unit("%")
+                                    .range(PlotRange::Fixed { min: 0.0, max: 100.0 })
+                                    .y_scale(battery_graph.y_scale)
+                                    .series("Charge", &battery_graph, theme.graph_line(0))
+                                    .draw(ui, &mut battery_graph.plot_view);
+                                Plot::new("Power Draw")
+                                    .unit(" W")
+                                    .y_scale(battery_graph.y_scale)
+                                    .series("Batteries", &power_draw_graph, theme.graph_line(1))
+                                    .draw(ui, &mut power_draw_graph.plot_view);
+                            } else {
+                                ui.text_disabled(
+                                    "Power supplies are only read on the local live system",
+                                );
                             }
+                            tab.end();
+                        }
+
+                        tab_bar.end();
+                    }
+                    restore_tab = None;
+                });
+        }
+
+        if config.panels.events {
+            ui.window("== Events ==")
+                .size(config.layout.events.size, layout_condition)
+                .position(config.layout.events.position, layout_condition)
+                .collapsed(true, Condition::FirstUseEver)
+                .build(|| {
+                    draw_events_table(ui, &event_watcher);
+                });
+        }
+
+        if config.panels.recorder {
+            ui.window("== Recorder ==")
+                .size(config.layout.recorder.size, layout_condition)
+                .position(config.layout.recorder.position, layout_condition)
+                .collapsed(true, Condition::FirstUseEver)
+                .build(|| {
+                    recorder_panel.draw(ui);
+                });
+        }
+
+        if config.panels.alerts {
+            ui.window("== Alerts ==")
+                .size(config.layout.alerts.size, layout_condition)
+                .position(config.layout.alerts.position, layout_condition)
+                .collapsed(true, Condition::FirstUseEver)
+                .build(|| {
+                    draw_alerts_table(ui, &alert_monitor);
+                });
+        }
 
-                            tab_bar.end();
+        if config.panels.cgroups {
+            ui.window("== Containers / Slices ==")
+                .size(config.layout.cgroups.size, layout_condition)
+                .position(config.layout.cgroups.position, layout_condition)
+                .collapsed(true, Condition::FirstUseEver)
+                .build(|| {
+                    if replay_frame.is_some() {
+                        ui.text_disabled("Cgroups are only available for the local live system");
+                        return;
+                    }
+                    if cgroup_reader.root != config.cgroups.root
+                        || cgroup_reader.proc_root != config.cgroups.proc_root
+                    {
+                        cgroup_reader =
+                            CgroupReader::new(&config.cgroups.root, &config.cgroups.proc_root);
+                        last_cgroup_refresh = None;
+                    }
+                    if last_cgroup_refresh
+                        .is_none_or(|last| last.elapsed() >= config.cgroups.refresh)
+                    {
+                        cgroup_tree = Some(cgroup_reader.read().map_err(|e| e.to_string()));
+                        last_cgroup_refresh = Some(Instant::now());
+                    }
+                    if let Some(tree) = &cgroup_tree {
+                        draw_cgroups(
+                            ui,
+                            tree.as_ref().map_err(String::as_str),
+                            &processes,
+                            &mut cgroup_view,
+                        );
+                    }
+                });
+        }
+
+        if config.panels.services {
+            ui.window("== Services ==")
+                .size(config.layout.services.size, layout_condition)
+                .position(config.layout.services.position, layout_condition)
+                .collapsed(true, Condition::FirstUseEver)
+                .build(|| {
+                    // L'état des unités n'est connu que pour le système local
+                    let local = replay_frame.is_none();
+                    if local {
+                        service_monitor.poll();
+                        service_monitor.systemctl = config.services.systemctl.clone();
+                        service_monitor.cgroup_root = config.cgroups.root.clone();
+                        if service_monitor
+                            .last_refresh
+                            .is_none_or(|last| last.elapsed() >= config.services.refresh)
+                        {
+                            service_monitor.refresh(&processes);
                         }
-                    });
-            });
+                    }
+                    let (no_states, no_cpu) = Default::default();
+                    let (states, cpu) = if local {
+                        (&service_monitor.states, &service_monitor.cpu)
+                    } else {
+                        (&no_states, &no_cpu)
+                    };
+                    draw_services(
+                        ui,
+                        &group_services(&processes, states, cpu),
+                        &processes,
+                        &mut service_view,
+                        service_monitor.error.as_deref().filter(|_| local),
+                        &theme.palette,
+                    );
+                });
+        }
 
-        ui.window("== Network ==")
-            .size([1260.0, 310.0], Condition::FirstUseEver)
-            .position([10.0, 390.0], Condition::FirstUseEver)
-            .build(|| {
-                // Code pour la fenêtre Réseau
-                if ui.button("IP-Address") {
-                    show_ip = !show_ip;
-                }
-                if show_ip {
-                    ui.separator();
-                   draw_ip_table(ui, &network);
-                }
-                ui.text("\n");
-                if let Some(tab_bar) = ui.tab_bar("Network") {
-                    draw_rx_table(ui, &network);
-                    draw_tx_table(ui, &network);
-                    tab_bar.end();
-                }
-                // Barres de Progressions
-                network_prog(ui, &mut show_rx_bar, &mut show_tx_bar, &network);
-            });
+        if config.panels.fleet && !remotes.is_empty() {
+            // Tuile locale: snapshot relu, sinon collecté en arrière-plan
+            let local = match &player {
+                Some(player) => Some(player.current().clone()),
+                None => local_sampler
+                    .get_or_insert_with(|| LocalSampler::start(config.sampling.alert_interval))
+                    .latest
+                    .lock()
+                    .unwrap()
+                    .clone(),
+            };
+            let hosts: Vec<HostSummary> = std::iter::once(HostSummary::from_snapshot(
+                &host_labels[0],
+                local.as_ref(),
+                RemoteStatus::Connected,
+                alert_monitor.engine.lock().unwrap().firing_count(),
+            ))
+            .chain(remotes.iter().map(|remote| {
+                HostSummary::from_snapshot(
+                    &remote.source.to_string(),
+                    remote.latest(),
+                    remote.status.lock().unwrap().clone(),
+                    remote.alerts.firing_count(),
+                )
+            }))
+            .collect();
+            ui.window("== Fleet ==")
+                .size(config.layout.fleet.size, layout_condition)
+                .position(config.layout.fleet.position, layout_condition)
+                .build(|| {
+                    if let Some(index) = draw_fleet(
+                        ui,
+                        &hosts,
+                        host_index,
+                        &mut fleet_view,
+                        &config.thresholds,
+                        &theme.palette,
+                        config.units.temperature,
+                    ) {
+                        selected_host = index;
+                    }
+                });
+        }
+
+        if let Some(player) = active_player(&mut player, &mut remotes, host_index) {
+            ui.window("== Replay ==")
+                .size(config.layout.replay.size, layout_condition)
+                .position(config.layout.replay.position, layout_condition)
+                .build(|| {
+                    if player.draw_controls(ui) {
+                        replay_loaded = false;
+                    }
+                });
+        }
+
+        let replay_network = replay_frame.as_ref().map(|frame| Network {
+            interfaces: frame.network.clone().unwrap_or_default(),
+        });
+        let network = replay_network.as_ref().unwrap_or(&network);
+        if config.panels.network {
+            ui.window("== Network ==")
+                .size(config.layout.network.size, layout_condition)
+                .position(config.layout.network.position, layout_condition)
+                .build(|| {
+                    // Code pour la fenêtre Réseau
+                    if ui.button("IP-Address") {
+                        show_ip = !show_ip;
+                    }
+                    if show_ip {
+                        ui.separator();
+                       draw_ip_table(ui, network);
+                    }
+                    ui.text("\n");
+                    if let Some(tab_bar) = ui.tab_bar("Network") {
+                        draw_rx_table(ui, network);
+                        draw_tx_table(ui, network);
+                        tab_bar.end();
+                    }
+                    // Barres de Progressions
+                    network_prog(
+                        ui,
+                        &mut show_rx_bar,
+                        &mut show_tx_bar,
+                        network,
+                        config.units.network_scale,
+                        &config.thresholds,
+                        &theme.palette,
+                    );
+                });
+        }
+
+        layout_condition = Condition::FirstUseEver;
 
         platform.prepare_render(&ui, &window);
+        let (width, height) = window.drawable_size();
         unsafe {
-            gl::Viewport(0, 0, 1280, 720);
+            gl::Viewport(0, 0, width as i32, height as i32);
             gl::ClearColor(
                 clear_color[0],
                 clear_color[1],
@@ -283,6 +1118,229 @@ async fn main() {
         }
         renderer.render(&mut imgui);
         window.gl_swap_window();
+
+        preferences = Preferences {
+            theme: Some(theme_name.clone()),
+            graphics_tab: graphics_tab.clone(),
+            graphs: [
+                ("cpu", cpu_graph.lock().unwrap().settings()),
+                ("fan", fan_graph.lock().unwrap().settings()),
+                ("temperature", temp_graph.lock().unwrap().settings()),
+                ("ram", ram_graph.settings()),
+                ("swap", swap_graph.settings()),
+                ("page_faults", pgfault_graph.settings()),
+                ("power", battery_graph.settings()),
+            ]
+            .into_iter()
+            .map(|(name, settings)| (name.to_string(), settings))
+            .collect(),
+            process_table: process_table.clone(),
+            selected_pids: selected_pids.iter().copied().collect(),
+            show_ip,
+            show_rx_bar,
+            show_tx_bar,
+        };
+        preference_store.update(&preferences);
     }
+    preference_store.save(&preferences);
 }
 
+// Snapshots de l'hôte affiché: session relue ou agent distant
+fn active_player<'a>(
+    player: &'a mut Option<SessionPlayer>,
+    remotes: &'a mut [RemoteHost],
+    host_index: usize,
+) -> Option<&'a mut SessionPlayer> {
+    match host_index {
+        0 => player.as_mut(),
+        index => remotes.get_mut(index - 1).map(|remote| &mut remote.player),
+    }
+}
+
+// Valeurs des graphiques reconstruites à partir d'une session
+enum ReplayValue {
+    CpuUsage,
+    FanSpeed,
+    Temperature(TemperatureUnit),
+    MemUsed,
+    MemCache,
+    MemAvailable,
+    RamPercent,
+    SwapPercent,
+    SwapIn,
+    SwapOut,
+    PageFaults,
+    MajorFaults,
+}
+
+fn load_replay_graphs(player: &SessionPlayer, graphs: &mut [(&mut graph::GraphData, ReplayValue)]) {
+    const MO: f32 = 1024.0 * 1024.0;
+    for (graph, value) in graphs.iter_mut() {
+        // Avec des archives, toute la session jusqu'à la position courante
+        let len = if graph.archives.is_empty() {
+            graph.max_points
+        } else {
+            usize::MAX
+        };
+        let values = match value {
+            ReplayValue::CpuUsage => player.series(len, |s| s.cpu.as_ref().map(|c| c.usage)),
+            ReplayValue::FanSpeed => player.series(len, |s| {
+                s.cpu
+                    .as_ref()
+                    .and_then(|c| c.fans.first().and_then(|fan| fan.rpm))
+                    .map(|rpm| rpm as f32)
+            }),
+            ReplayValue::Temperature(unit) => {
+                player.series(len, |s| s.cpu.as_ref().map(|c| unit.convert(c.temperature)))
+            }
+            ReplayValue::MemUsed => {
+                player.series(len, |s| s.meminfo.as_ref().map(|m| m.used() as f32 / MO))
+            }
+            ReplayValue::MemCache => {
+                player.series(len, |s| s.meminfo.as_ref().map(|m| m.cache() as f32 / MO))
+            }
+            ReplayValue::MemAvailable => player.series(len, |s| {
+                s.meminfo.as_ref().map(|m| m.mem_available as f32 / MO)
+            }),
+            ReplayValue::RamPercent => {
+                player.series(len, |s| s.meminfo.as_ref().map(|m| m.used_percent()))
+            }
+            ReplayValue::SwapPercent => {
+                player.series(len, |s| s.meminfo.as_ref().map(|m| m.swap_used_percent()))
+            }
+            ReplayValue::SwapIn => player.rate_series(len, |v| v.pswpin),
+            ReplayValue::SwapOut => player.rate_series(len, |v| v.pswpout),
+            ReplayValue::PageFaults => player.rate_series(len, |v| v.pgfault),
+            ReplayValue::MajorFaults => player.rate_series(len, |v| v.pgmajfault),
+        };
+        graph.set_samples(values);
+    }
+}
+
+async fn run_command(command: Command, metrics_prefix: &str, watcher: &ConfigWatcher, config: &Config) {
+    match command {
+        Command::Config => {
+            if let Some(error) = &watcher.error {
+                eprintln!("Configuration error: {}", error);
+                std::process::exit(1);
+            }
+            if let Some(path) = &watcher.path {
+                println!("# Loaded from {}", path.display());
+            }
+            match config.to_toml() {
+                Ok(toml) => print!("{}", toml),
+                Err(e) => {
+                    eprintln!("Error serializing configuration: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Snapshot(args) => {
+            if args.json {
+                match Snapshot::collect(&args.sections(), args.top).to_json() {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => {
+                        eprintln!("Error serializing snapshot: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+            let stdout = std::io::stdout();
+            let mut out = stdout.lock();
+            if let Err(e) = write_report(&mut out, &args.sections(), args.top) {
+                eprintln!("Error writing snapshot: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Tui => {
+            if let Err(e) = run_tui() {
+                eprintln!("Error running terminal UI: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Exporter(args) => {
+            let config = ExporterConfig {
+                bind: args.bind,
+                prefix: metrics_prefix.to_string(),
+                top_processes: args.top,
+            };
+            println!("Serving metrics on http://{}/metrics", config.bind);
+            if let Err(e) = serve_metrics(config).await {
+                eprintln!("Metrics exporter stopped: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Agent(args) => {
+            let config = AgentConfig {
+                bind: args.bind,
+                interval: args.interval,
+                backlog: args.backlog,
+                hostname: args.hostname,
+                fake: args.fake,
+            };
+            println!("Agent listening on {}", config.bind);
+            if let Err(e) = serve_agent(config).await {
+                eprintln!("Agent stopped: {}", e);
+                std::process::exit(1);
+            }
+        }
+        Command::Session(args) => {
+            let mut recorder = match SessionRecorder::start(&args.output, args.interval) {
+                Ok(recorder) => recorder,
+                Err(e) => {
+                    eprintln!("Error creating session {}: {}", args.output.display(), e);
+                    std::process::exit(1);
+                }
+            };
+            println!(
+                "Recording session to {} (Ctrl-C to stop)",
+                args.output.display()
+            );
+            match args.duration {
+                Some(duration) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep(duration) => {}
+                        _ = tokio::signal::ctrl_c() => {}
+                    }
+                }
+                None => {
+                    let _ = tokio::signal::ctrl_c().await;
+                }
+            }
+            recorder.stop();
+            let error = recorder.error.lock().unwrap().clone();
+            if let Some(error) = error {
+                eprintln!("Session recording failed: {}", error);
+                std::process::exit(1);
+            }
+        }
+        Command::Record(args) => {
+            let mut recorder = match Recorder::start(args.config()) {
+                Ok(recorder) => recorder,
+                Err(e) => {
+                    eprintln!("Error starting recorder: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            println!("Recording to {} (Ctrl-C to stop)", args.dir.display());
+            match args.duration {
+                Some(duration) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep(duration) => {}
+                        _ = tokio::signal::ctrl_c() => {}
+                    }
+                }
+                None => {
+                    let _ = tokio::signal::ctrl_c().await;
+                }
+            }
+            recorder.stop();
+            let error = recorder.error.lock().unwrap().clone();
+            if let Some(error) = error {
+                eprintln!("Recording failed: {}", error);
+                std::process::exit(1);
+            }
+        }
+    }
+}
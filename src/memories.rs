@@ -1,6 +1,11 @@
 pub mod memory_util {
+    use std::{fs, io, path::Path, time::Duration};
+
+    use imgui::{ImColor32, ProgressBar, Ui};
+    use serde::{Deserialize, Serialize};
     use sysinfo::{Disks, System};
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Swap {
         pub total_swap: u64,
         pub used_swap: u64,
@@ -15,6 +20,7 @@ pub mod memory_util {
             }
         }
     }
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Ram {
         pub total_ram: u64,
         pub used_ram: u64,
@@ -29,6 +35,7 @@ pub mod memory_util {
             }
         }
     }
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Disk {
         pub total_disk: u64,
         pub used_disk: u64,
@@ -43,6 +50,38 @@ pub mod memory_util {
             }
         }
     }
+    // Occupation d'un système de fichiers monté
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct DiskUsage {
+        pub name: String,
+        pub mount_point: String,
+        pub total_space: u64,
+        pub available_space: u64,
+    }
+
+    impl DiskUsage {
+        pub fn list() -> Vec<DiskUsage> {
+            Disks::new_with_refreshed_list()
+                .iter()
+                .map(|disk| DiskUsage {
+                    name: disk.name().to_string_lossy().to_string(),
+                    mount_point: disk.mount_point().to_string_lossy().to_string(),
+                    total_space: disk.total_space(),
+                    available_space: disk.available_space(),
+                })
+                .collect()
+        }
+
+        pub fn used_percent(&self) -> f32 {
+            if self.total_space == 0 {
+                return 0.0;
+            }
+            self.total_space.saturating_sub(self.available_space) as f32 * 100.0
+                / self.total_space as f32
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Memory {
         pub storage: Disk,
         pub swap: Swap,
@@ -65,16 +104,289 @@ pub mod memory_util {
             // RAM and swap information:
             self.ram.total_ram = sys.total_memory();
             self.ram.used_ram = sys.used_memory();
-            self.ram.free_ram = self.ram.total_ram - self.ram.used_ram;
+            self.ram.free_ram = self.ram.total_ram.saturating_sub(self.ram.used_ram);
             // swap information:
             self.swap.total_swap = sys.total_swap();
             self.swap.used_swap = sys.used_swap();
-            self.swap.free_swap = self.swap.total_swap - self.swap.used_swap ;
+            self.swap.free_swap = self.swap.total_swap.saturating_sub(self.swap.used_swap);
             // disk information:
+            // Sans disque visible (conteneurs, VM minimales), le stockage reste à zéro
             let disks = Disks::new_with_refreshed_list();
-            self.storage.free_disk = disks[0].available_space();
-            self.storage.total_disk = disks[0].total_space();
-            self.storage.used_disk = self.storage.total_disk - self.storage.free_disk;
+            if let Some(disk) = disks.first() {
+                self.storage.free_disk = disk.available_space();
+                self.storage.total_disk = disk.total_space();
+                self.storage.used_disk = self.storage.total_disk.saturating_sub(self.storage.free_disk);
+            }
+        }
+    }
+
+    // Détail complet de /proc/meminfo (toutes les valeurs sont en octets)
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct MemInfo {
+        pub mem_total: u64,
+        pub mem_free: u64,
+        pub mem_available: u64,
+        pub buffers: u64,
+        pub cached: u64,
+        pub swap_cached: u64,
+        pub shmem: u64,
+        pub s_reclaimable: u64,
+        pub s_unreclaim: u64,
+        pub dirty: u64,
+        pub writeback: u64,
+        pub anon_pages: u64,
+        pub mapped: u64,
+        pub hugepages_total: u64,
+        pub hugepages_free: u64,
+        pub hugepage_size: u64,
+        pub committed_as: u64,
+        pub commit_limit: u64,
+        pub swap_total: u64,
+        pub swap_free: u64,
+    }
+
+    impl MemInfo {
+        pub fn read() -> io::Result<MemInfo> {
+            Self::from_path("/proc/meminfo")
+        }
+
+        pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<MemInfo> {
+            let content = fs::read_to_string(path)?;
+            Ok(Self::parse(&content))
+        }
+
+        pub fn parse(content: &str) -> MemInfo {
+            let mut info = MemInfo::default();
+            for line in content.lines() {
+                let mut parts = line.split_whitespace();
+                let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+                    continue;
+                };
+                let value = value.parse::<u64>().unwrap_or(0);
+                // Les valeurs sont exprimées en kB, sauf les compteurs de hugepages
+                let bytes = match parts.next() {
+                    Some("kB") => value * 1024,
+                    _ => value,
+                };
+                match key.trim_end_matches(':') {
+                    "MemTotal" => info.mem_total = bytes,
+                    "MemFree" => info.mem_free = bytes,
+                    "MemAvailable" => info.mem_available = bytes,
+                    "Buffers" => info.buffers = bytes,
+                    "Cached" => info.cached = bytes,
+                    "SwapCached" => info.swap_cached = bytes,
+                    "Shmem" => info.shmem = bytes,
+                    "SReclaimable" => info.s_reclaimable = bytes,
+                    "SUnreclaim" => info.s_unreclaim = bytes,
+                    "Dirty" => info.dirty = bytes,
+                    "Writeback" => info.writeback = bytes,
+                    "AnonPages" => info.anon_pages = bytes,
+                    "Mapped" => info.mapped = bytes,
+                    "HugePages_Total" => info.hugepages_total = bytes,
+                    "HugePages_Free" => info.hugepages_free = bytes,
+                    "Hugepagesize" => info.hugepage_size = bytes,
+                    "Committed_AS" => info.committed_as = bytes,
+                    "CommitLimit" => info.commit_limit = bytes,
+                    "SwapTotal" => info.swap_total = bytes,
+                    "SwapFree" => info.swap_free = bytes,
+                    _ => {}
+                }
+            }
+            info
+        }
+
+        // Même calcul que `free`: buff/cache comprend le slab récupérable
+        pub fn cache(&self) -> u64 {
+            self.cached + self.s_reclaimable
+        }
+
+        pub fn used(&self) -> u64 {
+            self.mem_total
+                .saturating_sub(self.mem_free)
+                .saturating_sub(self.buffers)
+                .saturating_sub(self.cache())
+        }
+
+        pub fn used_percent(&self) -> f32 {
+            if self.mem_total == 0 {
+                return 0.0;
+            }
+            self.used() as f32 * 100.0 / self.mem_total as f32
+        }
+
+        pub fn swap_used_percent(&self) -> f32 {
+            if self.swap_total == 0 {
+                return 0.0;
+            }
+            self.swap_total.saturating_sub(self.swap_free) as f32 * 100.0 / self.swap_total as f32
+        }
+
+        pub fn slab(&self) -> u64 {
+            self.s_reclaimable + self.s_unreclaim
+        }
+
+        pub fn hugepages_used(&self) -> u64 {
+            self.hugepages_total.saturating_sub(self.hugepages_free) * self.hugepage_size
+        }
+
+        // Segments de la barre empilée: (nom, octets, couleur)
+        pub fn segments(&self) -> [(&'static str, u64, [f32; 4]); 5] {
+            [
+                ("Used", self.used(), [0.85, 0.25, 0.25, 1.0]),
+                ("Shared", self.shmem, [0.85, 0.45, 0.85, 1.0]),
+                ("Buffers", self.buffers, [0.30, 0.55, 0.95, 1.0]),
+                (
+                    "Cache",
+                    self.cache().saturating_sub(self.shmem),
+                    [0.95, 0.80, 0.25, 1.0],
+                ),
+                ("Free", self.mem_free, [0.30, 0.80, 0.35, 1.0]),
+            ]
+        }
+    }
+
+    // Compteurs de pagination de /proc/vmstat (cumulés depuis le démarrage)
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    pub struct VmStat {
+        pub pswpin: u64,
+        pub pswpout: u64,
+        pub pgfault: u64,
+        pub pgmajfault: u64,
+    }
+
+    // Taux par seconde calculés entre deux lectures de /proc/vmstat
+    #[derive(Debug, Clone, Default)]
+    pub struct VmStatRates {
+        pub swap_in: f32,
+        pub swap_out: f32,
+        pub pgfault: f32,
+        pub pgmajfault: f32,
+    }
+
+    impl VmStat {
+        pub fn read() -> io::Result<VmStat> {
+            Self::from_path("/proc/vmstat")
+        }
+
+        pub fn from_path<P: AsRef<Path>>(path: P) -> io::Result<VmStat> {
+            let content = fs::read_to_string(path)?;
+            Ok(Self::parse(&content))
+        }
+
+        pub fn parse(content: &str) -> VmStat {
+            let mut stat = VmStat::default();
+            for line in content.lines() {
+                let mut parts = line.split_whitespace();
+                let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+                    continue;
+                };
+                let value = value.parse::<u64>().unwrap_or(0);
+                match key {
+                    "pswpin" => stat.pswpin = value,
+                    "pswpout" => stat.pswpout = value,
+                    "pgfault" => stat.pgfault = value,
+                    "pgmajfault" => stat.pgmajfault = value,
+                    _ => {}
+                }
+            }
+            stat
+        }
+
+        pub fn rates_since(&self, previous: &VmStat, elapsed: Duration) -> VmStatRates {
+            let secs = elapsed.as_secs_f32();
+            if secs <= 0.0 {
+                return VmStatRates::default();
+            }
+            let rate = |now: u64, before: u64| now.saturating_sub(before) as f32 / secs;
+            VmStatRates {
+                swap_in: rate(self.pswpin, previous.pswpin),
+                swap_out: rate(self.pswpout, previous.pswpout),
+                pgfault: rate(self.pgfault, previous.pgfault),
+                pgmajfault: rate(self.pgmajfault, previous.pgmajfault),
+            }
+        }
+    }
+
+    // Barre empilée montrant la répartition réelle de la RAM
+    pub fn draw_memory_breakdown(ui: &Ui, info: &MemInfo) {
+        if info.mem_total == 0 {
+            ui.text("/proc/meminfo unavailable");
+            return;
+        }
+        let segments = info.segments();
+        let draw_list = ui.get_window_draw_list();
+        let pos = ui.cursor_screen_pos();
+        let size = [400.0, 24.0];
+        let mut x = pos[0];
+        for (_, bytes, color) in &segments {
+            let width = size[0] * (*bytes as f32 / info.mem_total as f32);
+            if width > 0.0 {
+                draw_list
+                    .add_rect([x, pos[1]], [x + width, pos[1] + size[1]], *color)
+                    .filled(true)
+                    .build();
+            }
+            x += width;
+        }
+        draw_list
+            .add_rect(pos, [pos[0] + size[0], pos[1] + size[1]], ImColor32::WHITE)
+            .build();
+        ui.invisible_button("memory_breakdown", size);
+        if ui.is_item_hovered() {
+            ui.tooltip(|| {
+                for (name, bytes, _) in &segments {
+                    ui.text(format!("{}: {}", name, convert_bytes_to_any(*bytes)));
+                }
+            });
+        }
+
+        // Légende
+        for (i, (name, bytes, color)) in segments.iter().enumerate() {
+            if i > 0 {
+                ui.same_line();
+            }
+            ui.text_colored(*color, format!("{} {}", name, convert_bytes_to_any(*bytes)));
+        }
+
+        if let Some(_node) = ui.tree_node("Details") {
+            let rows = [
+                ("Available", info.mem_available),
+                ("Buffers", info.buffers),
+                ("Cached", info.cached),
+                ("Swap Cached", info.swap_cached),
+                ("Shmem", info.shmem),
+                ("Slab Reclaimable", info.s_reclaimable),
+                ("Slab Unreclaimable", info.s_unreclaim),
+                ("Dirty", info.dirty),
+                ("Writeback", info.writeback),
+                ("Anon Pages", info.anon_pages),
+                ("Mapped", info.mapped),
+                ("HugePages Used", info.hugepages_used()),
+                ("HugePages Total", info.hugepages_total * info.hugepage_size),
+            ];
+            ui.columns(2, "MemInfoColumns", true);
+            for (name, bytes) in rows {
+                ui.text(name);
+                ui.next_column();
+                ui.text(convert_bytes_to_any(bytes));
+                ui.next_column();
+            }
+            ui.columns(1, "", false);
+
+            let commit = if info.commit_limit > 0 {
+                info.committed_as as f32 / info.commit_limit as f32
+            } else {
+                0.0
+            };
+            ui.text("Committed_AS / CommitLimit:");
+            ProgressBar::new(commit.min(1.0))
+                .size([300.0, 24.0])
+                .overlay_text(format!(
+                    "{} / {}",
+                    convert_bytes_to_any(info.committed_as),
+                    convert_bytes_to_any(info.commit_limit)
+                ))
+                .build(ui);
         }
     }
 
@@ -1,11 +1,13 @@
 pub mod network {
     use imgui::{ImColor32, Ui};
+    use serde::{Deserialize, Serialize};
     use std::net::Ipv4Addr;
     use sysinfo::Networks;
 
-    use crate::convert_bytes_to_any;
+    use crate::{convert_bytes_to_any, Palette, ThresholdsConfig};
     extern crate pnet;
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct RxStats {
         pub bytes: u64,
         pub packets: u64,
@@ -17,6 +19,7 @@ pub mod network {
         pub multicast: u64,
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct TxStats {
         pub bytes: u64,
         pub packets: u64,
@@ -28,6 +31,7 @@ pub mod network {
         pub compressed: u64,
     }
 
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Interface {
         pub name: String,
         pub ip: Ipv4Addr,
@@ -253,18 +257,19 @@ pub mod network {
         }
     }
 
-    pub fn network_prog(ui: &Ui, show_rx_bar: &mut bool, show_tx_bar: &mut bool, stats: &Network) {
-        const MAX: f32 = 1024.0 * 1024.0 * 1024.0 * 2.0; // 2GB en bytes
-
-        fn get_color(value: f32) -> [f32; 4] {
-            if value <= MAX / 2.0 {
-                [0.0, 1.0, 0.0, 1.0] // Vert
-            } else if value > MAX / 2.0 && value <= MAX * 2.0 / 3.0 {
-                [1.0, 1.0, 0.0, 1.0] // Jaune
-            } else {
-                [1.0, 0.0, 0.0, 1.0] // Rouge
-            }
-        }
+    // `scale`: pleine échelle des barres en octets (units.network_scale)
+    pub fn network_prog(
+        ui: &Ui,
+        show_rx_bar: &mut bool,
+        show_tx_bar: &mut bool,
+        stats: &Network,
+        scale: u64,
+        thresholds: &ThresholdsConfig,
+        palette: &Palette,
+    ) {
+        let max = scale as f32;
+        let get_color =
+            |value: f32| thresholds.color(value as f64 * 100.0 / scale as f64, palette);
 
         ui.text("\n");
         if ui.button("Network-Receiver") {
@@ -282,7 +287,7 @@ pub mod network {
 
         if *show_rx_bar {
             for stat in &stats.interfaces {
-                let rx = stat.total_received as f32 / MAX;
+                let rx = stat.total_received as f32 / max;
                 let color = get_color(stat.total_received as f32);
                 let (r, g, b) = (color[0], color[1], color[2]);
                 ui.text(&stat.name);
@@ -313,7 +318,7 @@ pub mod network {
                 }
                 ui.invisible_button("progress_bar", size);
 
-                let label = format!("{}", convert_bytes_to_any(MAX as u64));
+                let label = format!("{}", convert_bytes_to_any(scale));
                 ui.same_line_with_spacing(0.0, 10.0); // Pour afficher à droite de la barre
                 ui.text(&label);
                 ui.text("\n");
@@ -322,7 +327,7 @@ pub mod network {
 
         if *show_tx_bar {
             for stat in &stats.interfaces {
-                let tx = stat.total_transmitted as f32 / MAX;
+                let tx = stat.total_transmitted as f32 / max;
                 let color = get_color(stat.total_transmitted as f32);
                 let (r, g, b) = (color[0], color[1], color[2]);
                 ui.text(&stat.name);
@@ -351,7 +356,7 @@ pub mod network {
 
                 ui.invisible_button("progress_bar", size);
 
-                let label = format!("{}", convert_bytes_to_any(MAX as u64));
+                let label = format!("{}", convert_bytes_to_any(scale));
                 ui.same_line_with_spacing(0.0, 10.0); // Pour afficher à droite de la barre
                 ui.text(&label);
                 ui.text("\n");
@@ -0,0 +1,365 @@
+pub mod notification {
+    use std::{
+        collections::HashMap,
+        fs::OpenOptions,
+        io::{self, Read, Write},
+        net::{TcpStream, ToSocketAddrs},
+        path::PathBuf,
+        process::{Command, Stdio},
+        sync::{
+            mpsc::{self, Sender},
+            Arc, Mutex,
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{parse_duration, AlertState, AlertTransition};
+
+    // Destination d'une notification, choisie par le champ "type" du fichier de règles
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type", rename_all = "lowercase")]
+    pub enum SinkTarget {
+        // Notification de bureau freedesktop via D-Bus
+        Desktop,
+        // POST JSON vers une URL http://
+        Webhook { url: String },
+        // Commande shell, l'alerte est décrite par des variables d'environnement
+        Command { command: String },
+        // Ajout d'une ligne dans un fichier
+        Log { path: PathBuf },
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SinkConfig {
+        pub name: String,
+        #[serde(flatten)]
+        pub target: SinkTarget,
+        // Intervalle minimal entre deux notifications d'une même règle ("5m")
+        #[serde(default)]
+        pub rate_limit: Option<String>,
+        // Notifier aussi le retour à la normale
+        #[serde(default = "default_resolved")]
+        pub resolved: bool,
+    }
+
+    fn default_resolved() -> bool {
+        true
+    }
+
+    pub struct NotificationSink {
+        pub name: String,
+        pub target: SinkTarget,
+        pub rate_limit: Duration,
+        pub resolved: bool,
+        // Limite par règle et par état: la fin d'une alerte n'est pas masquée par son début
+        last_sent: HashMap<(String, AlertState), Instant>,
+    }
+
+    impl NotificationSink {
+        pub fn from_config(config: &SinkConfig) -> Result<NotificationSink, String> {
+            let rate_limit = match &config.rate_limit {
+                Some(limit) => {
+                    parse_duration(limit).map_err(|e| format!("sink '{}': {}", config.name, e))?
+                }
+                None => Duration::ZERO,
+            };
+            if let SinkTarget::Webhook { url } = &config.target {
+                parse_http_url(url).map_err(|e| format!("sink '{}': {}", config.name, e))?;
+            }
+            Ok(NotificationSink {
+                name: config.name.clone(),
+                target: config.target.clone(),
+                rate_limit,
+                resolved: config.resolved,
+                last_sent: HashMap::new(),
+            })
+        }
+
+        // Vrai si la règle n'a pas été notifiée dans cet état sur ce canal depuis rate_limit
+        fn allow(&mut self, rule: &str, state: AlertState, now: Instant) -> bool {
+            let key = (rule.to_string(), state);
+            match self.last_sent.get(&key) {
+                Some(last) if now.duration_since(*last) < self.rate_limit => false,
+                _ => {
+                    self.last_sent.insert(key, now);
+                    true
+                }
+            }
+        }
+
+        pub fn send(&self, transition: &AlertTransition) -> io::Result<()> {
+            match &self.target {
+                SinkTarget::Desktop => send_desktop(transition),
+                SinkTarget::Webhook { url } => send_webhook(url, transition),
+                SinkTarget::Command { command } => run_command(command, transition),
+                SinkTarget::Log { path } => {
+                    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+                    writeln!(file, "{}", log_line(transition))
+                }
+            }
+        }
+    }
+
+    pub fn summary(transition: &AlertTransition) -> String {
+        format!(
+            "[{}] {} {}",
+            transition.severity,
+            transition.rule,
+            transition.to.label().to_lowercase()
+        )
+    }
+
+    pub fn log_line(transition: &AlertTransition) -> String {
+        format!(
+            "{} {} (was {}): {} (value {}) on {}",
+            transition.timestamp.to_rfc3339(),
+            summary(transition),
+            transition.from.label().to_lowercase(),
+            transition.expr,
+            format_value(transition.value),
+            transition.hostname
+        )
+    }
+
+    fn format_value(value: Option<f64>) -> String {
+        value
+            .map(|value| format!("{:.2}", value))
+            .unwrap_or_else(|| "N/A".to_string())
+    }
+
+    // Chaîne GVariant entre apostrophes pour gdbus
+    fn gvariant_string(text: &str) -> String {
+        format!("'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+
+    fn send_desktop(transition: &AlertTransition) -> io::Result<()> {
+        let summary = summary(transition);
+        let body = format!(
+            "{}\nvalue: {}\nhost: {}",
+            transition.expr,
+            format_value(transition.value),
+            transition.hostname
+        );
+        let urgency = match (transition.to, transition.severity.as_str()) {
+            (AlertState::Firing, "critical") => 2,
+            (AlertState::Firing, _) => 1,
+            _ => 0,
+        };
+        let status = Command::new("gdbus")
+            .args([
+                "call",
+                "--session",
+                "--dest",
+                "org.freedesktop.Notifications",
+                "--object-path",
+                "/org/freedesktop/Notifications",
+                "--method",
+                "org.freedesktop.Notifications.Notify",
+                "'system_monitor'",
+                "0",
+                "''",
+                &gvariant_string(&summary),
+                &gvariant_string(&body),
+                "[]",
+                &format!("{{'urgency': <byte {}>}}", urgency),
+                "-1",
+            ])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let status = match status {
+            // Sans gdbus, notify-send parle au même service D-Bus
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Command::new("notify-send")
+                .args([
+                    "--app-name=system_monitor",
+                    &format!("--urgency={}", ["low", "normal", "critical"][urgency]),
+                    &summary,
+                    &body,
+                ])
+                .status()?,
+            other => other?,
+        };
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "desktop notification failed: {}",
+                status
+            )))
+        }
+    }
+
+    // (hôte:port, chemin) d'une URL http://
+    pub fn parse_http_url(url: &str) -> io::Result<(String, String)> {
+        let rest = url.strip_prefix("http://").ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("only http:// webhooks are supported: {}", url),
+            )
+        })?;
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], &rest[index..]),
+            None => (rest, "/"),
+        };
+        if authority.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("missing host in {}", url),
+            ));
+        }
+        let authority = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{}:80", authority)
+        };
+        Ok((authority, path.to_string()))
+    }
+
+    fn send_webhook(url: &str, transition: &AlertTransition) -> io::Result<()> {
+        let (authority, path) = parse_http_url(url)?;
+        let body = serde_json::to_string(transition)?;
+        let address = authority.to_socket_addrs()?.next().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("cannot resolve {}", authority),
+            )
+        })?;
+        let mut stream = TcpStream::connect_timeout(&address, Duration::from_secs(5))?;
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        stream.set_write_timeout(Some(Duration::from_secs(5)))?;
+        // Requête envoyée d'un bloc: certains serveurs ne lisent qu'un segment
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: system_monitor\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            path,
+            authority,
+            body.len(),
+            body
+        );
+        stream.write_all(request.as_bytes())?;
+        let mut response = Vec::new();
+        let mut buffer = [0; 512];
+        // Seule la ligne de statut nous intéresse
+        while !response.contains(&b'\n') {
+            let read = stream.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            response.extend_from_slice(&buffer[..read]);
+        }
+        let response = String::from_utf8_lossy(&response);
+        let status_line = response.lines().next().unwrap_or_default();
+        match status_line.split_whitespace().nth(1) {
+            Some(code) if code.starts_with('2') => Ok(()),
+            _ => Err(io::Error::other(format!(
+                "webhook {} answered '{}'",
+                url, status_line
+            ))),
+        }
+    }
+
+    fn run_command(command: &str, transition: &AlertTransition) -> io::Result<()> {
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .env("ALERT_NAME", &transition.rule)
+            .env("ALERT_EXPR", &transition.expr)
+            .env("ALERT_SEVERITY", &transition.severity)
+            .env("ALERT_STATE", transition.to.label().to_lowercase())
+            .env(
+                "ALERT_PREVIOUS_STATE",
+                transition.from.label().to_lowercase(),
+            )
+            .env("ALERT_VALUE", format_value(transition.value))
+            .env("ALERT_TIMESTAMP", transition.timestamp.to_rfc3339())
+            .env("ALERT_HOSTNAME", &transition.hostname)
+            .stdin(Stdio::null())
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!(
+                "'{}' exited with {}",
+                command, status
+            )))
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    pub struct NotifierStatus {
+        pub sent: u64,
+        pub suppressed: u64,
+        pub failed: u64,
+        pub last_error: Option<String>,
+    }
+
+    // Distribue les transitions aux canaux dans un thread dédié, pour ne pas
+    // bloquer l'évaluation sur un webhook lent
+    pub struct Notifier {
+        sender: Option<Sender<AlertTransition>>,
+        pub sink_names: Vec<String>,
+        pub status: Arc<Mutex<NotifierStatus>>,
+    }
+
+    impl Notifier {
+        pub fn spawn(mut sinks: Vec<NotificationSink>) -> Notifier {
+            let status = Arc::new(Mutex::new(NotifierStatus::default()));
+            let sink_names = sinks.iter().map(|sink| sink.name.clone()).collect();
+            if sinks.is_empty() {
+                return Notifier {
+                    sender: None,
+                    sink_names,
+                    status,
+                };
+            }
+            let (sender, receiver) = mpsc::channel::<AlertTransition>();
+            let thread_status = status.clone();
+            thread::spawn(move || {
+                for transition in receiver {
+                    let now = Instant::now();
+                    for sink in sinks.iter_mut() {
+                        let routed = transition
+                            .sinks
+                            .as_ref()
+                            .is_none_or(|names| names.contains(&sink.name));
+                        if !routed || (transition.to == AlertState::Resolved && !sink.resolved) {
+                            continue;
+                        }
+                        if !sink.allow(&transition.rule, transition.to, now) {
+                            thread_status.lock().unwrap().suppressed += 1;
+                            continue;
+                        }
+                        let result = sink.send(&transition);
+                        let mut status = thread_status.lock().unwrap();
+                        match result {
+                            Ok(()) => status.sent += 1,
+                            Err(e) => {
+                                status.failed += 1;
+                                status.last_error = Some(format!("{}: {}", sink.name, e));
+                            }
+                        }
+                    }
+                }
+            });
+            Notifier {
+                sender: Some(sender),
+                sink_names,
+                status,
+            }
+        }
+
+        // Seules les alertes qui se déclenchent ou se résolvent sont notifiées
+        pub fn notify(&self, transitions: &[AlertTransition]) {
+            let Some(sender) = &self.sender else {
+                return;
+            };
+            for transition in transitions {
+                if matches!(transition.to, AlertState::Firing | AlertState::Resolved) {
+                    let _ = sender.send(transition.clone());
+                }
+            }
+        }
+    }
+}
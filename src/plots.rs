@@ -0,0 +1,481 @@
+pub mod plot {
+    use chrono::{DateTime, Local, TimeZone, Utc};
+    use imgui::{MouseButton, StyleColor, Ui};
+    use std::time::Duration;
+
+    use crate::{
+        compute_percentile, compute_stats, downsample, format_duration, Bucket, GraphData,
+    };
+
+    // Durée minimale affichée après un zoom (en millisecondes)
+    const MIN_SPAN: i64 = 2_000;
+
+    // Plage de l'axe des valeurs
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PlotRange {
+        // Ajustée aux valeurs affichées (multipliée par le Y Scale)
+        Auto,
+        // Bornes fixes, ex: 0-100 pour un pourcentage
+        Fixed { min: f32, max: f32 },
+    }
+
+    // Zoom et défilement horizontal d'un graphique (instants en millisecondes)
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct PlotView {
+        // Durée affichée, None pour toute la plage
+        pub span: Option<i64>,
+        // Fin de la fenêtre, None pour suivre la donnée la plus récente
+        pub end: Option<i64>,
+    }
+
+    impl PlotView {
+        pub fn is_zoomed(&self) -> bool {
+            self.span.is_some()
+        }
+
+        pub fn reset(&mut self) {
+            *self = PlotView::default();
+        }
+
+        // Fenêtre visible pour des données couvrant [first, last]
+        pub fn window(&self, first: i64, last: i64) -> (i64, i64) {
+            let full = (last - first).max(1);
+            let span = self
+                .span
+                .map_or(full, |span| span.clamp(MIN_SPAN.min(full), full));
+            let end = self.end.map_or(last, |end| end.clamp(first + span, last));
+            (end - span, end)
+        }
+
+        // Zoom d'un facteur (< 1 pour agrandir) en gardant `anchor` sous la souris
+        pub fn zoom(&mut self, factor: f64, anchor: i64, first: i64, last: i64) {
+            let (start, end) = self.window(first, last);
+            let full = (last - first).max(1);
+            let span = ((end - start) as f64 * factor).round() as i64;
+            if span >= full {
+                self.reset();
+                return;
+            }
+            let span = span.max(MIN_SPAN.min(full));
+            let ratio = (anchor - start) as f64 / (end - start).max(1) as f64;
+            let end = (anchor + ((1.0 - ratio) * span as f64) as i64).clamp(first + span, last);
+            self.span = Some(span);
+            self.end = (end < last).then_some(end);
+        }
+
+        // Décale la fenêtre de `delta` millisecondes (négatif vers le passé)
+        pub fn pan(&mut self, delta: i64, first: i64, last: i64) {
+            if !self.is_zoomed() {
+                return;
+            }
+            let (start, end) = self.window(first, last);
+            let end = (end + delta).clamp(first + (end - start), last);
+            self.end = (end < last).then_some(end);
+        }
+    }
+
+    // Série d'un graphique: agrégats de la plage choisie avec "Range"
+    pub struct PlotSeries {
+        pub label: String,
+        pub color: [f32; 4],
+        pub buckets: Vec<Bucket>,
+    }
+
+    // Graphique à plusieurs séries avec légende, axes, infobulle, zoom (molette),
+    // défilement (glisser) et retour à toute la plage (double-clic)
+    pub struct Plot {
+        pub label: String,
+        pub unit: String,
+        pub range: PlotRange,
+        pub y_scale: f32,
+        pub size: [f32; 2],
+        pub series: Vec<PlotSeries>,
+        pub markers: Vec<DateTime<Utc>>,
+        pub marker_color: [f32; 4],
+    }
+
+    impl Plot {
+        pub const WIDTH: f32 = 500.0;
+        pub const HEIGHT: f32 = 100.0;
+
+        pub fn new(label: &str) -> Plot {
+            Plot {
+                label: label.to_string(),
+                unit: String::new(),
+                range: PlotRange::Auto,
+                y_scale: 1.0,
+                size: [Self::WIDTH, Self::HEIGHT],
+                series: Vec::new(),
+                markers: Vec::new(),
+                marker_color: [1.0, 0.3, 0.3, 1.0],
+            }
+        }
+
+        // Suffixe des valeurs, ex: "%" ou " Mo"
+        pub fn unit(mut self, unit: &str) -> Plot {
+            self.unit = unit.to_string();
+            self
+        }
+
+        pub fn range(mut self, range: PlotRange) -> Plot {
+            self.range = range;
+            self
+        }
+
+        pub fn y_scale(mut self, y_scale: f32) -> Plot {
+            self.y_scale = y_scale;
+            self
+        }
+
+        // Ajoute la plage affichée d'un graphique et ses événements marqués
+        pub fn series(mut self, label: &str, graph: &GraphData, color: [f32; 4]) -> Plot {
+            self.series.push(PlotSeries {
+                label: label.to_string(),
+                color,
+                buckets: graph.view_buckets(),
+            });
+            self.markers.extend(graph.markers.iter().copied());
+            self.marker_color = graph.marker_color;
+            self
+        }
+
+        fn format_value(&self, value: f32) -> String {
+            format!("{:.2}{}", value, self.unit)
+        }
+
+        fn format_axis(&self, value: f32) -> String {
+            let precision = match value.abs() {
+                v if v >= 1000.0 => 0,
+                v if v >= 10.0 => 1,
+                _ => 2,
+            };
+            format!("{:.*}{}", precision, value, self.unit)
+        }
+
+        // Bornes de l'axe des valeurs pour les agrégats visibles. Une plage fixe n'est
+        // pas étirée par `y_scale`
+        fn value_range(&self, visible: &[Vec<Bucket>]) -> (f32, f32) {
+            let (min, max) = match self.range {
+                PlotRange::Fixed { min, max } => return (min, max),
+                PlotRange::Auto => {
+                    let (min, max) = visible
+                        .iter()
+                        .flatten()
+                        .fold((f32::MAX, f32::MIN), |(min, max), bucket| {
+                            (min.min(bucket.avg()), max.max(bucket.avg()))
+                        });
+                    if min > max {
+                        (0.0, 1.0)
+                    } else {
+                        // Marge autour des données, même quand elles sont constantes
+                        let pad = if (max - min).abs() < f32::EPSILON {
+                            min.abs().max(1.0) * 0.1
+                        } else {
+                            (max - min) * 0.05
+                        };
+                        let low = if min >= 0.0 {
+                            (min - pad).max(0.0)
+                        } else {
+                            min - pad
+                        };
+                        (low, max + pad)
+                    }
+                }
+            };
+            (
+                min,
+                min + (max - min).max(f32::EPSILON) * self.y_scale.max(1.0),
+            )
+        }
+
+        pub fn draw(mut self, ui: &Ui, view: &mut PlotView) {
+            let style = ui.clone_style();
+            let disabled = style.colors[StyleColor::TextDisabled as usize];
+            let grid_color = [disabled[0], disabled[1], disabled[2], 0.3];
+            let line_height = ui.text_line_height();
+
+            // Légende: nom du graphique puis couleur, nom et dernière valeur de chaque série
+            ui.text(&self.label);
+            for series in &self.series {
+                ui.same_line();
+                let pos = ui.cursor_screen_pos();
+                ui.get_window_draw_list()
+                    .add_rect(
+                        [pos[0], pos[1] + line_height * 0.25],
+                        [pos[0] + line_height * 0.5, pos[1] + line_height * 0.75],
+                        series.color,
+                    )
+                    .filled(true)
+                    .build();
+                ui.dummy([line_height * 0.5, line_height]);
+                ui.same_line();
+                let last = series.buckets.last().map(|bucket| bucket.avg());
+                ui.text(match last {
+                    Some(value) => format!("{} {}", series.label, self.format_value(value)),
+                    None => series.label.clone(),
+                });
+            }
+
+            let first = self
+                .series
+                .iter()
+                .filter_map(|series| series.buckets.first())
+                .map(|bucket| bucket.start.timestamp_millis())
+                .min();
+            let last = self
+                .series
+                .iter()
+                .filter_map(|series| series.buckets.last())
+                .map(|bucket| bucket.start.timestamp_millis())
+                .max();
+            let (first, last) = match (first, last) {
+                (Some(first), Some(last)) => (first, last),
+                _ => {
+                    ui.invisible_button(&self.label, self.size);
+                    let rect_min = ui.item_rect_min();
+                    ui.get_window_draw_list().add_text(
+                        [rect_min[0] + style.frame_padding[0], rect_min[1]],
+                        disabled,
+                        "No data",
+                    );
+                    return;
+                }
+            };
+            let (start, end) = view.window(first, last);
+            let span = (end - start).max(1);
+
+            // Agrégats visibles, réduits à un point par pixel
+            let visible: Vec<Vec<Bucket>> = self
+                .series
+                .iter()
+                .map(|series| {
+                    let buckets: Vec<Bucket> = series
+                        .buckets
+                        .iter()
+                        .filter(|bucket| (start..=end).contains(&bucket.start.timestamp_millis()))
+                        .copied()
+                        .collect();
+                    downsample(&buckets, self.size[0] as usize)
+                })
+                .collect();
+            let (low, high) = self.value_range(&visible);
+            let value_labels = [high, (low + high) / 2.0, low].map(|value| self.format_axis(value));
+
+            // Zone du graphique, à droite des valeurs de l'axe vertical
+            let axis_width = value_labels
+                .iter()
+                .map(|text| ui.calc_text_size(text)[0])
+                .fold(0.0, f32::max)
+                + style.item_spacing[0];
+            ui.invisible_button(&self.label, self.size);
+            let hovered = ui.is_item_hovered();
+            let active = ui.is_item_active();
+            let rect_min = ui.item_rect_min();
+            let plot_min = [rect_min[0] + axis_width, rect_min[1]];
+            let plot_max = [rect_min[0] + self.size[0], rect_min[1] + self.size[1]];
+            let plot_width = (plot_max[0] - plot_min[0]).max(1.0);
+            let plot_height = (plot_max[1] - plot_min[1]).max(1.0);
+            let x_of = |time: i64| plot_min[0] + plot_width * (time - start) as f32 / span as f32;
+            let y_of = |value: f32| plot_max[1] - plot_height * (value - low) / (high - low);
+            let time_of = |x: f32| start + ((x - plot_min[0]) / plot_width * span as f32) as i64;
+
+            let mouse = ui.io().mouse_pos;
+            let mouse_inside = hovered
+                && (plot_min[0]..=plot_max[0]).contains(&mouse[0])
+                && (plot_min[1]..=plot_max[1]).contains(&mouse[1]);
+
+            {
+                let draw_list = ui.get_window_draw_list();
+                draw_list
+                    .add_rect(
+                        plot_min,
+                        plot_max,
+                        style.colors[StyleColor::FrameBg as usize],
+                    )
+                    .filled(true)
+                    .rounding(style.frame_rounding)
+                    .build();
+
+                // Grille et valeurs de l'axe vertical: haut, milieu, bas
+                for (index, text) in value_labels.iter().enumerate() {
+                    let y = plot_min[1] + plot_height * index as f32 / 2.0;
+                    draw_list
+                        .add_line([plot_min[0], y], [plot_max[0], y], grid_color)
+                        .build();
+                    let text_y =
+                        (y - line_height / 2.0).clamp(plot_min[1], plot_max[1] - line_height);
+                    let text_x = plot_min[0] - style.item_spacing[0] - ui.calc_text_size(text)[0];
+                    draw_list.add_text([text_x, text_y], disabled, text);
+                }
+
+                draw_list.with_clip_rect_intersect(plot_min, plot_max, || {
+                    for index in 1..4 {
+                        let x = plot_min[0] + plot_width * index as f32 / 4.0;
+                        draw_list
+                            .add_line([x, plot_min[1]], [x, plot_max[1]], grid_color)
+                            .build();
+                    }
+
+                    // Lignes verticales pour les événements marqués, placées selon leur heure
+                    self.markers.sort();
+                    self.markers.dedup();
+                    for marker in &self.markers {
+                        let time = marker.timestamp_millis();
+                        if !(start..=end).contains(&time) {
+                            continue;
+                        }
+                        let x = x_of(time);
+                        draw_list
+                            .add_line([x, plot_min[1]], [x, plot_max[1]], self.marker_color)
+                            .build();
+                    }
+
+                    for (series, buckets) in self.series.iter().zip(&visible) {
+                        let points: Vec<[f32; 2]> = buckets
+                            .iter()
+                            .map(|bucket| {
+                                [x_of(bucket.start.timestamp_millis()), y_of(bucket.avg())]
+                            })
+                            .collect();
+                        match points.as_slice() {
+                            [] => {}
+                            [point] => draw_list
+                                .add_circle(*point, 2.0, series.color)
+                                .filled(true)
+                                .build(),
+                            _ => draw_list
+                                .add_polyline(points, series.color)
+                                .thickness(1.5)
+                                .build(),
+                        }
+                    }
+                });
+            }
+
+            // Infobulle: heure et valeur exactes du point le plus proche de la souris
+            if mouse_inside {
+                let time = time_of(mouse[0]);
+                let nearest: Vec<Option<&Bucket>> = visible
+                    .iter()
+                    .map(|buckets| {
+                        buckets
+                            .iter()
+                            .min_by_key(|bucket| (bucket.start.timestamp_millis() - time).abs())
+                    })
+                    .collect();
+                {
+                    let draw_list = ui.get_window_draw_list();
+                    draw_list
+                        .add_line([mouse[0], plot_min[1]], [mouse[0], plot_max[1]], disabled)
+                        .build();
+                    for (series, bucket) in self.series.iter().zip(&nearest) {
+                        if let Some(bucket) = bucket {
+                            let point = [x_of(bucket.start.timestamp_millis()), y_of(bucket.avg())];
+                            draw_list
+                                .add_circle(point, 3.0, series.color)
+                                .filled(true)
+                                .build();
+                        }
+                    }
+                }
+                ui.tooltip(|| {
+                    let timestamp = nearest
+                        .iter()
+                        .flatten()
+                        .map(|bucket| bucket.start)
+                        .next()
+                        .unwrap_or_else(|| Utc.timestamp_millis_opt(time).unwrap());
+                    ui.text(
+                        timestamp
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M:%S%.3f")
+                            .to_string(),
+                    );
+                    for (series, bucket) in self.series.iter().zip(&nearest) {
+                        let Some(bucket) = bucket else {
+                            continue;
+                        };
+                        let value = if bucket.is_empty() {
+                            format!("{} (no samples)", self.format_value(bucket.avg()))
+                        } else if bucket.count > 1 {
+                            format!(
+                                "{} (min {}, max {}, {} samples)",
+                                self.format_value(bucket.avg()),
+                                self.format_value(bucket.min),
+                                self.format_value(bucket.max),
+                                bucket.count
+                            )
+                        } else {
+                            self.format_value(bucket.avg())
+                        };
+                        ui.text_colored(series.color, format!("{}: {}", series.label, value));
+                    }
+                });
+            }
+
+            // Molette: zoom autour de la souris, glisser: défilement, double-clic: tout afficher
+            if mouse_inside {
+                let wheel = ui.io().mouse_wheel;
+                if wheel != 0.0 {
+                    view.zoom(0.8f64.powf(wheel as f64), time_of(mouse[0]), first, last);
+                }
+                if ui.is_mouse_double_clicked(MouseButton::Left) {
+                    view.reset();
+                }
+            }
+            if active && ui.is_mouse_dragging(MouseButton::Left) {
+                let delta = -ui.io().mouse_delta[0] / plot_width * span as f32;
+                view.pan(delta as i64, first, last);
+            }
+
+            // Axe du temps: début, milieu et fin de la fenêtre affichée
+            let format = if span > 86_400_000 {
+                "%m-%d %H:%M"
+            } else {
+                "%H:%M:%S"
+            };
+            let local = |time: i64| {
+                Utc.timestamp_millis_opt(time)
+                    .unwrap()
+                    .with_timezone(&Local)
+                    .format(format)
+                    .to_string()
+            };
+            let labels = [local(start), local(start + span / 2), local(end)];
+            let y = ui.cursor_screen_pos()[1];
+            {
+                let draw_list = ui.get_window_draw_list();
+                for (index, text) in labels.iter().enumerate() {
+                    let text_width = ui.calc_text_size(text)[0];
+                    let x = plot_min[0] + plot_width * index as f32 / 2.0
+                        - text_width * index as f32 / 2.0;
+                    draw_list.add_text([x, y], disabled, text);
+                }
+            }
+            ui.dummy([self.size[0], line_height]);
+
+            // Statistiques de la fenêtre affichée
+            for (series, buckets) in self.series.iter().zip(&visible) {
+                let Some(stats) = compute_stats(buckets) else {
+                    continue;
+                };
+                let p95 = compute_percentile(buckets, 95.0).unwrap_or(stats.max);
+                let prefix = if self.series.len() > 1 {
+                    format!("{}: ", series.label)
+                } else {
+                    String::new()
+                };
+                ui.text_disabled(format!(
+                    "{}min {:.2}  avg {:.2}  max {:.2}  p95 {:.2}",
+                    prefix, stats.min, stats.avg, stats.max, p95
+                ));
+            }
+            if view.is_zoomed() {
+                ui.text_disabled(format!(
+                    "Zoom: {} (double-click to reset)",
+                    format_duration(Duration::from_millis(span as u64))
+                ));
+            }
+        }
+    }
+}
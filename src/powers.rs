@@ -0,0 +1,323 @@
+pub mod power {
+    use std::{fs, path::Path, time::Duration};
+
+    use imgui::{ProgressBar, Ui};
+
+    pub const DEFAULT_POWER_SUPPLY_ROOT: &str = "/sys/class/power_supply";
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SupplyKind {
+        Battery,
+        Mains,
+        Usb,
+        Other(String),
+    }
+
+    impl SupplyKind {
+        fn parse(kind: &str) -> SupplyKind {
+            match kind {
+                "Battery" => SupplyKind::Battery,
+                "Mains" => SupplyKind::Mains,
+                "USB" => SupplyKind::Usb,
+                other => SupplyKind::Other(other.to_string()),
+            }
+        }
+
+        pub fn label(&self) -> &str {
+            match self {
+                SupplyKind::Battery => "Battery",
+                SupplyKind::Mains => "AC adapter",
+                SupplyKind::Usb => "USB",
+                SupplyKind::Other(kind) => kind,
+            }
+        }
+    }
+
+    // Une entrée de /sys/class/power_supply; les énergies sont en Wh et la puissance en W
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PowerSupply {
+        pub name: String,
+        pub kind: SupplyKind,
+        // Adaptateurs: branché ou non
+        pub online: Option<bool>,
+        // Charging, Discharging, Full, Not charging, Unknown
+        pub status: Option<String>,
+        pub capacity: Option<f32>,
+        pub energy_now: Option<f64>,
+        pub energy_full: Option<f64>,
+        pub energy_full_design: Option<f64>,
+        pub power_now: Option<f64>,
+        pub cycle_count: Option<u32>,
+        // Attribut "health" du pilote (ex: Good), quand il existe
+        pub health_status: Option<String>,
+        pub model: Option<String>,
+        pub technology: Option<String>,
+    }
+
+    impl PowerSupply {
+        // Les pilotes exposent soit energy_* (µWh) et power_now (µW), soit charge_* (µAh)
+        // et current_now (µA), convertis avec la tension (µV)
+        pub fn read(dir: &Path) -> Option<PowerSupply> {
+            let text = |name: &str| {
+                fs::read_to_string(dir.join(name))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let number = |name: &str| text(name).and_then(|value| value.parse::<f64>().ok());
+            let kind = SupplyKind::parse(&text("type")?);
+            let voltage = number("voltage_min_design")
+                .or_else(|| number("voltage_now"))
+                .map(|microvolts| microvolts / 1e6);
+            let energy = |suffix: &str| {
+                number(&format!("energy_{}", suffix))
+                    .map(|uwh| uwh / 1e6)
+                    .or_else(|| {
+                        let charge = number(&format!("charge_{}", suffix))?;
+                        Some(charge / 1e6 * voltage?)
+                    })
+            };
+            let power_now = number("power_now").map(|uw| uw / 1e6).or_else(|| {
+                let current = number("current_now")?;
+                let voltage = number("voltage_now")?;
+                Some((current / 1e6 * voltage / 1e6).abs())
+            });
+            Some(PowerSupply {
+                name: dir.file_name()?.to_string_lossy().to_string(),
+                kind,
+                online: number("online").map(|online| online > 0.0),
+                status: text("status"),
+                capacity: number("capacity").map(|capacity| capacity as f32),
+                energy_now: energy("now"),
+                energy_full: energy("full"),
+                energy_full_design: energy("full_design"),
+                power_now,
+                cycle_count: number("cycle_count")
+                    .filter(|cycles| *cycles > 0.0)
+                    .map(|cycles| cycles as u32),
+                health_status: text("health"),
+                model: text("model_name"),
+                technology: text("technology"),
+            })
+        }
+
+        pub fn is_battery(&self) -> bool {
+            self.kind == SupplyKind::Battery
+        }
+
+        // Charge en %: l'attribut capacity, sinon calculée à partir des énergies
+        pub fn charge(&self) -> Option<f32> {
+            self.capacity
+                .or_else(|| match (self.energy_now, self.energy_full) {
+                    (Some(now), Some(full)) if full > 0.0 => Some((now * 100.0 / full) as f32),
+                    _ => None,
+                })
+        }
+
+        // Capacité actuelle en % de la capacité d'origine
+        pub fn health(&self) -> Option<f32> {
+            match (self.energy_full, self.energy_full_design) {
+                (Some(full), Some(design)) if design > 0.0 => Some((full * 100.0 / design) as f32),
+                _ => None,
+            }
+        }
+
+        pub fn is_charging(&self) -> bool {
+            self.status.as_deref() == Some("Charging")
+        }
+
+        pub fn is_discharging(&self) -> bool {
+            self.status.as_deref() == Some("Discharging")
+        }
+    }
+
+    // Toutes les alimentations sous `root`, triées par nom (vide si le dossier n'existe pas)
+    pub fn read_power_supplies(root: &Path) -> Vec<PowerSupply> {
+        let mut supplies: Vec<PowerSupply> = fs::read_dir(root)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|entry| PowerSupply::read(&entry.path()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        supplies.sort_by(|a, b| a.name.cmp(&b.name));
+        supplies
+    }
+
+    // Durée restante à la puissance actuelle
+    pub fn estimate_duration(energy: f64, power: f64) -> Option<Duration> {
+        if energy < 0.0 || power <= 0.0 || !(energy / power).is_finite() {
+            return None;
+        }
+        Some(Duration::from_secs_f64(energy / power * 3600.0))
+    }
+
+    // Ensemble des batteries vu comme une seule (machines à plusieurs batteries)
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct BatteryTotals {
+        pub charge: f32,
+        pub power: Option<f64>,
+        pub charging: bool,
+        pub discharging: bool,
+        pub time_to_empty: Option<Duration>,
+        pub time_to_full: Option<Duration>,
+        // Un adaptateur secteur est branché
+        pub on_ac: bool,
+    }
+
+    impl BatteryTotals {
+        pub fn from_supplies(supplies: &[PowerSupply]) -> Option<BatteryTotals> {
+            let batteries: Vec<&PowerSupply> = supplies
+                .iter()
+                .filter(|supply| supply.is_battery())
+                .collect();
+            if batteries.is_empty() {
+                return None;
+            }
+            let energy_now: Option<f64> = batteries.iter().map(|battery| battery.energy_now).sum();
+            let energy_full: Option<f64> =
+                batteries.iter().map(|battery| battery.energy_full).sum();
+            let charge = match (energy_now, energy_full) {
+                (Some(now), Some(full)) if full > 0.0 => (now * 100.0 / full) as f32,
+                _ => {
+                    batteries
+                        .iter()
+                        .filter_map(|battery| battery.charge())
+                        .sum::<f32>()
+                        / batteries.len() as f32
+                }
+            };
+            let power = batteries
+                .iter()
+                .filter_map(|battery| battery.power_now)
+                .reduce(|a, b| a + b);
+            let charging = batteries.iter().any(|battery| battery.is_charging());
+            let discharging = batteries.iter().any(|battery| battery.is_discharging());
+            let time_to_empty = match (discharging, energy_now, power) {
+                (true, Some(now), Some(power)) => estimate_duration(now, power),
+                _ => None,
+            };
+            let time_to_full = match (charging, energy_now, energy_full, power) {
+                (true, Some(now), Some(full), Some(power)) => estimate_duration(full - now, power),
+                _ => None,
+            };
+            let on_ac = supplies
+                .iter()
+                .any(|supply| !supply.is_battery() && supply.online == Some(true));
+            Some(BatteryTotals {
+                charge,
+                power,
+                charging,
+                discharging,
+                time_to_empty,
+                time_to_full,
+                on_ac,
+            })
+        }
+    }
+
+    pub fn format_hours(duration: Duration) -> String {
+        let minutes = duration.as_secs() / 60;
+        format!("{}h{:02}", minutes / 60, minutes % 60)
+    }
+
+    // Ligne de la fenêtre Système, ex: "Power: AC, battery 85% charging (0h40 to full)"
+    pub fn draw_power_summary(ui: &Ui, supplies: &[PowerSupply]) {
+        let Some(totals) = BatteryTotals::from_supplies(supplies) else {
+            if supplies.iter().any(|supply| supply.online == Some(true)) {
+                ui.text("Power: AC adapter");
+            }
+            return;
+        };
+        let source = if totals.on_ac { "AC" } else { "battery" };
+        let state = if totals.charging {
+            "charging"
+        } else if totals.discharging {
+            "discharging"
+        } else {
+            "idle"
+        };
+        let mut line = format!("Power: {}, battery {:.0}% {}", source, totals.charge, state);
+        if let Some(time) = totals.time_to_empty {
+            line.push_str(&format!(" ({} left)", format_hours(time)));
+        } else if let Some(time) = totals.time_to_full {
+            line.push_str(&format!(" ({} to full)", format_hours(time)));
+        }
+        ui.text(line);
+    }
+
+    // Détail de chaque alimentation (onglet Power)
+    pub fn draw_power_supplies(ui: &Ui, root: &Path, supplies: &[PowerSupply]) {
+        if supplies.is_empty() {
+            ui.text_disabled(format!("No power supply found in {}", root.display()));
+            return;
+        }
+        if let Some(totals) = BatteryTotals::from_supplies(supplies) {
+            match (totals.time_to_empty, totals.time_to_full) {
+                (Some(time), _) => ui.text(format!("Time to empty: {}", format_hours(time))),
+                (_, Some(time)) => ui.text(format!("Time to full: {}", format_hours(time))),
+                _ => ui.text("Time remaining: N/A"),
+            }
+        }
+        let watt_hours = |value: Option<f64>| match value {
+            Some(value) => format!("{:.1} Wh", value),
+            None => "N/A".to_string(),
+        };
+        for supply in supplies {
+            let mut title = format!("{} ({})", supply.name, supply.kind.label());
+            if let Some(model) = &supply.model {
+                title.push_str(&format!(" - {}", model));
+            }
+            if !supply.is_battery() {
+                let online = match supply.online {
+                    Some(true) => "online",
+                    Some(false) => "offline",
+                    None => "unknown",
+                };
+                ui.text(format!("{}: {}", title, online));
+                continue;
+            }
+            ui.text(title);
+            let charge = supply.charge().unwrap_or(0.0);
+            ProgressBar::new(charge / 100.0)
+                .size([300.0, 20.0])
+                .overlay_text(format!(
+                    "{:.0}% {}",
+                    charge,
+                    supply.status.as_deref().unwrap_or("")
+                ))
+                .build(ui);
+            ui.text(format!(
+                "Energy: {} / {} (design {})",
+                watt_hours(supply.energy_now),
+                watt_hours(supply.energy_full),
+                watt_hours(supply.energy_full_design)
+            ));
+            ui.text(format!(
+                "Power draw: {}",
+                match supply.power_now {
+                    Some(power) => format!("{:.2} W", power),
+                    None => "N/A".to_string(),
+                }
+            ));
+            let mut details = Vec::new();
+            if let Some(health) = supply.health() {
+                details.push(format!("Health: {:.0}%", health));
+            }
+            if let Some(status) = &supply.health_status {
+                details.push(format!("Condition: {}", status));
+            }
+            if let Some(cycles) = supply.cycle_count {
+                details.push(format!("Cycles: {}", cycles));
+            }
+            if let Some(technology) = &supply.technology {
+                details.push(technology.clone());
+            }
+            if !details.is_empty() {
+                ui.text(details.join("   "));
+            }
+            ui.separator();
+        }
+    }
+}
@@ -0,0 +1,114 @@
+pub mod preference {
+    use std::{
+        collections::BTreeMap,
+        fs, io,
+        path::{Path, PathBuf},
+        time::{Duration, Instant},
+    };
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{user_config_dir, GraphSettings, ProcessTableState};
+
+    pub const PREFERENCES_FILE_NAME: &str = "preferences.toml";
+    pub const IMGUI_INI_FILE_NAME: &str = "imgui.ini";
+
+    // Fichier ini d'ImGui (positions, tailles, fenêtres repliées) dans le répertoire de configuration
+    pub fn imgui_ini_path() -> Option<PathBuf> {
+        let dir = user_config_dir()?;
+        fs::create_dir_all(&dir).ok()?;
+        Some(dir.join(IMGUI_INI_FILE_NAME))
+    }
+
+    pub fn preferences_path() -> Option<PathBuf> {
+        user_config_dir().map(|dir| dir.join(PREFERENCES_FILE_NAME))
+    }
+
+    // Choix faits dans l'interface, restaurés au lancement suivant
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct Preferences {
+        // Thème choisi dans la fenêtre System
+        pub theme: Option<String>,
+        // Onglet ouvert dans la fenêtre Graphics
+        pub graphics_tab: Option<String>,
+        pub graphs: BTreeMap<String, GraphSettings>,
+        pub process_table: ProcessTableState,
+        pub selected_pids: Vec<usize>,
+        pub show_ip: bool,
+        pub show_rx_bar: bool,
+        pub show_tx_bar: bool,
+    }
+
+    impl Preferences {
+        pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Preferences> {
+            let content = fs::read_to_string(path)?;
+            toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        }
+
+        pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+            let content = toml::to_string(self).map_err(io::Error::other)?;
+            if let Some(dir) = path.as_ref().parent() {
+                fs::create_dir_all(dir)?;
+            }
+            // Écriture dans un fichier temporaire puis renommage: pas de fichier tronqué
+            let tmp = path.as_ref().with_extension("toml.tmp");
+            fs::write(&tmp, content)?;
+            fs::rename(tmp, path)
+        }
+    }
+
+    // Sauvegarde les préférences quand elles changent, au plus toutes les `interval`
+    pub struct PreferenceStore {
+        pub path: Option<PathBuf>,
+        pub error: Option<String>,
+        saved: Preferences,
+        last_save: Instant,
+    }
+
+    impl PreferenceStore {
+        pub const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+        pub fn load() -> (PreferenceStore, Preferences) {
+            let path = preferences_path();
+            let mut error = None;
+            let preferences = match &path {
+                Some(path) if path.exists() => Preferences::load(path).unwrap_or_else(|e| {
+                    error = Some(format!("{}: {}", path.display(), e));
+                    Preferences::default()
+                }),
+                _ => Preferences::default(),
+            };
+            let store = PreferenceStore {
+                path,
+                error,
+                saved: preferences.clone(),
+                last_save: Instant::now(),
+            };
+            (store, preferences)
+        }
+
+        pub fn update(&mut self, preferences: &Preferences) {
+            if self.last_save.elapsed() >= Self::SAVE_INTERVAL {
+                self.save(preferences);
+            }
+        }
+
+        pub fn save(&mut self, preferences: &Preferences) {
+            self.last_save = Instant::now();
+            let Some(path) = &self.path else {
+                return;
+            };
+            if *preferences == self.saved {
+                return;
+            }
+            match preferences.save(path) {
+                Ok(()) => {
+                    self.saved = preferences.clone();
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(format!("{}: {}", path.display(), e)),
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod process {
     use std::{
+        cmp::Ordering,
         collections::HashSet,
         fs,
         io::{BufRead, BufReader, Read},
@@ -7,19 +8,24 @@ pub mod process {
     };
 
     use imgui::Ui;
+    use serde::{Deserialize, Serialize};
     use sysinfo::{Process, System};
 
-    #[derive(Debug, Clone)]
+    use crate::{read_process_cgroup, unit_from_cgroup, DEFAULT_PROC_ROOT};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct ProcessInfo {
         pub pid: usize,
         pub name: String,
         pub state: String,
         pub cpu_usage: f32,
         pub memory_usage: f32,
+        // Chemin du cgroup v2, ex: /system.slice/docker-<id>.scope (vide si inconnu)
+        pub cgroup: String,
     }
 
     impl ProcessInfo {
-        fn from_process(process: &Process, system: &System) -> Self {
+        fn from_process(process: &Process, system: &System, proc_root: &Path) -> Self {
             let pid = process.pid();
             let name = process.name().to_string();
             let state = process.status().to_string();
@@ -32,6 +38,7 @@ pub mod process {
                 state,
                 cpu_usage,
                 memory_usage,
+                cgroup: read_process_cgroup(proc_root, pid.as_u32() as usize).unwrap_or_default(),
             }
         }
     }
@@ -42,11 +49,19 @@ pub mod process {
         system
             .processes()
             .iter()
-            .map(|(_, process)| ProcessInfo::from_process(process, system))
+            .map(|(_, process)| {
+                ProcessInfo::from_process(process, system, Path::new(DEFAULT_PROC_ROOT))
+            })
             .collect()
     }
 
     pub fn get_process_info() -> Vec<ProcessInfo> {
+        get_process_info_with_cgroups(Path::new(DEFAULT_PROC_ROOT))
+    }
+
+    // Le cgroup est lu sous `cgroup_proc_root` (config `cgroups.proc_root`), comme pour
+    // la fenêtre "Containers / Slices"
+    pub fn get_process_info_with_cgroups(cgroup_proc_root: &Path) -> Vec<ProcessInfo> {
         let mut processes = Vec::new();
 
         let proc_dir = Path::new("/proc");
@@ -62,8 +77,12 @@ pub mod process {
                                 state: String::new(),
                                 cpu_usage: 0.0,
                                 memory_usage: 0.0,
+                                cgroup: String::new(),
                             };
 
+                            process.cgroup = read_process_cgroup(cgroup_proc_root, pid)
+                                .unwrap_or_default();
+
                             // Lire le nom du processus à partir du fichier cmdline
                             let cmdline_path = path.join("cmdline");
                             if let Ok(cmdline_file) = fs::File::open(&cmdline_path) {
@@ -89,7 +108,9 @@ pub mod process {
                                         process.state = parts[2].to_string();
                                         let utime = parts[13].parse::<f32>().unwrap_or(0.0);
                                         let stime = parts[14].parse::<f32>().unwrap_or(0.0);
-                                        process.cpu_usage = (utime * 100.0) / (utime + stime);
+                                        if utime + stime > 0.0 {
+                                            process.cpu_usage = (utime * 100.0) / (utime + stime);
+                                        }
                                     }
                                 }
                             }
@@ -133,64 +154,184 @@ pub mod process {
         processes
     }
 
-    // Process Table
-    pub fn draw_process_table(ui: &Ui, _system: &mut System, selected_pids: &mut HashSet<usize>) {
-        // Obtenez les informations des processus
-        let processes = get_process_info();
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ProcessColumn {
+        Pid,
+        Name,
+        State,
+        Cpu,
+        Memory,
+        Unit,
+    }
+
+    impl ProcessColumn {
+        pub const ALL: [ProcessColumn; 6] = [
+            ProcessColumn::Pid,
+            ProcessColumn::Name,
+            ProcessColumn::State,
+            ProcessColumn::Cpu,
+            ProcessColumn::Memory,
+            ProcessColumn::Unit,
+        ];
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                ProcessColumn::Pid => "PID",
+                ProcessColumn::Name => "Name",
+                ProcessColumn::State => "State",
+                ProcessColumn::Cpu => "CPU Usage",
+                ProcessColumn::Memory => "Memory Usage",
+                ProcessColumn::Unit => "Unit",
+            }
+        }
+
+        fn compare(&self, a: &ProcessInfo, b: &ProcessInfo) -> Ordering {
+            match self {
+                ProcessColumn::Pid => a.pid.cmp(&b.pid),
+                ProcessColumn::Name => a.name.cmp(&b.name),
+                ProcessColumn::State => a.state.cmp(&b.state),
+                ProcessColumn::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+                ProcessColumn::Memory => a.memory_usage.total_cmp(&b.memory_usage),
+                ProcessColumn::Unit => {
+                    unit_from_cgroup(&a.cgroup).cmp(&unit_from_cgroup(&b.cgroup))
+                }
+            }
+        }
+    }
+
+    // Recherche, tri et colonnes affichées de la table (conservés entre les lancements)
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct ProcessTableState {
+        pub search: String,
+        pub sort_column: ProcessColumn,
+        pub sort_descending: bool,
+        pub hidden_columns: Vec<ProcessColumn>,
+    }
+
+    impl Default for ProcessTableState {
+        fn default() -> Self {
+            ProcessTableState {
+                search: String::new(),
+                sort_column: ProcessColumn::Pid,
+                sort_descending: false,
+                hidden_columns: Vec::new(),
+            }
+        }
+    }
+
+    impl ProcessTableState {
+        // Le nom porte la sélection: il reste toujours affiché
+        pub fn visible_columns(&self) -> Vec<ProcessColumn> {
+            ProcessColumn::ALL
+                .into_iter()
+                .filter(|column| {
+                    *column == ProcessColumn::Name || !self.hidden_columns.contains(column)
+                })
+                .collect()
+        }
 
-        // Afficher le champ de filtre
-        let mut search_buffer = String::new();
+        // Un clic sur la colonne triée inverse l'ordre
+        pub fn sort_by(&mut self, column: ProcessColumn) {
+            if self.sort_column == column {
+                self.sort_descending = !self.sort_descending;
+            } else {
+                self.sort_column = column;
+                self.sort_descending = false;
+            }
+        }
+    }
 
+    // Process Table
+    pub fn draw_process_table(
+        ui: &Ui,
+        processes: &[ProcessInfo],
+        selected_pids: &mut HashSet<usize>,
+        state: &mut ProcessTableState,
+    ) {
         ui.text("Process Informations:");
         ui.text(format!("Total Processes: {}", processes.len()));
-        ui.input_text("Search", &mut search_buffer).build();
-
-        // Afficher le tableau
-        ui.columns(5, "ProcessColumns", true);
-        ui.text("PID");
-        ui.next_column();
-        ui.text("Name");
-        ui.next_column();
-        ui.text("State");
-        ui.next_column();
-        ui.text("CPU Usage");
-        ui.next_column();
-        ui.text("Memory Usage");
-        ui.next_column();
+        ui.input_text("Search", &mut state.search).build();
+        ui.same_line();
+        if ui.button("Columns") {
+            ui.open_popup("process_columns");
+        }
+        ui.popup("process_columns", || {
+            for column in ProcessColumn::ALL {
+                if column == ProcessColumn::Name {
+                    continue;
+                }
+                let mut visible = !state.hidden_columns.contains(&column);
+                if ui.checkbox(column.label(), &mut visible) {
+                    if visible {
+                        state.hidden_columns.retain(|hidden| *hidden != column);
+                    } else {
+                        state.hidden_columns.push(column);
+                    }
+                }
+            }
+        });
+
+        // Afficher le tableau, les en-têtes servent à trier
+        let columns = state.visible_columns();
+        ui.columns(columns.len() as i32, "ProcessColumns", true);
+        for column in &columns {
+            let mut header = column.label().to_string();
+            if *column == state.sort_column {
+                header.push_str(if state.sort_descending { " v" } else { " ^" });
+            }
+            if ui.selectable(&header) {
+                state.sort_by(*column);
+            }
+            ui.next_column();
+        }
         ui.separator();
 
         // Filtrez les processus en fonction de la recherche
-        let filtered_processes: Vec<&ProcessInfo> = processes
+        let mut filtered_processes: Vec<&ProcessInfo> = processes
             .iter()
-            .filter(|p| p.name.contains(&search_buffer))
+            .filter(|p| p.name.contains(&state.search))
             .collect();
+        filtered_processes.sort_by(|a, b| {
+            let ordering = state.sort_column.compare(a, b);
+            if state.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
 
         for process in filtered_processes {
-            ui.text(format!("{}", process.pid));
-            ui.next_column();
-            let is_selected = selected_pids.contains(&process.pid);
-                        
-            if ui
-                .selectable_config(&process.name)
-                .selected(is_selected)
-                .build()
-            {
-                if is_selected {
-                    selected_pids.remove(&process.pid);
-                } else {
-                    selected_pids.insert(process.pid);
+            for column in &columns {
+                match column {
+                    ProcessColumn::Pid => ui.text(format!("{}", process.pid)),
+                    ProcessColumn::Name => {
+                        let is_selected = selected_pids.contains(&process.pid);
+                        if ui
+                            .selectable_config(&process.name)
+                            .selected(is_selected)
+                            .build()
+                        {
+                            if is_selected {
+                                selected_pids.remove(&process.pid);
+                            } else {
+                                selected_pids.insert(process.pid);
+                            }
+                        }
+                    }
+                    ProcessColumn::State => ui.text(&process.state),
+                    ProcessColumn::Cpu => ui.text(format!("{:.2}%", process.cpu_usage)),
+                    ProcessColumn::Memory => ui.text(format!("{:.2}%", process.memory_usage)),
+                    ProcessColumn::Unit => match unit_from_cgroup(&process.cgroup) {
+                        Some(unit) => ui.text(unit),
+                        None => ui.text_disabled("-"),
+                    },
                 }
+                ui.next_column();
             }
-
-            ui.next_column();
-            ui.text(&process.state);
-            ui.next_column();
-            ui.text(format!("{:.2}%", process.cpu_usage));
-            ui.next_column();
-            ui.text(format!("{:.2}%", process.memory_usage));
-            ui.next_column();
             ui.separator();
         }
         ui.columns(1, "", false); // Reset columns
     }
-}
+}
\ No newline at end of file
@@ -0,0 +1,645 @@
+pub mod recording {
+    use std::{
+        collections::HashMap,
+        fmt, fs,
+        fs::File,
+        io::{self, BufWriter, Write},
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    use chrono::{DateTime, Utc};
+    use imgui::Ui;
+    use serde::Serialize;
+    use sysinfo::{Components, Networks, System};
+
+    use crate::{Cpu, MemInfo, VmStat};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Metric {
+        CpuUsage,
+        CpuTemperature,
+        FanSpeed,
+        RamUsed,
+        RamCache,
+        RamAvailable,
+        SwapUsed,
+        SwapIn,
+        SwapOut,
+        PageFaults,
+        MajorFaults,
+        NetworkRx,
+        NetworkTx,
+    }
+
+    impl Metric {
+        pub const ALL: [Metric; 13] = [
+            Metric::CpuUsage,
+            Metric::CpuTemperature,
+            Metric::FanSpeed,
+            Metric::RamUsed,
+            Metric::RamCache,
+            Metric::RamAvailable,
+            Metric::SwapUsed,
+            Metric::SwapIn,
+            Metric::SwapOut,
+            Metric::PageFaults,
+            Metric::MajorFaults,
+            Metric::NetworkRx,
+            Metric::NetworkTx,
+        ];
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                Metric::CpuUsage => "cpu_usage",
+                Metric::CpuTemperature => "cpu_temperature",
+                Metric::FanSpeed => "fan_speed",
+                Metric::RamUsed => "ram_used",
+                Metric::RamCache => "ram_cache",
+                Metric::RamAvailable => "ram_available",
+                Metric::SwapUsed => "swap_used",
+                Metric::SwapIn => "swap_in",
+                Metric::SwapOut => "swap_out",
+                Metric::PageFaults => "page_faults",
+                Metric::MajorFaults => "major_faults",
+                Metric::NetworkRx => "network_rx",
+                Metric::NetworkTx => "network_tx",
+            }
+        }
+
+        pub fn unit(&self) -> &'static str {
+            match self {
+                Metric::CpuUsage => "%",
+                Metric::CpuTemperature => "°C",
+                Metric::FanSpeed => "RPM",
+                Metric::RamUsed | Metric::RamCache | Metric::RamAvailable | Metric::SwapUsed => {
+                    "bytes"
+                }
+                Metric::SwapIn | Metric::SwapOut => "pages/s",
+                Metric::PageFaults | Metric::MajorFaults => "faults/s",
+                Metric::NetworkRx | Metric::NetworkTx => "bytes/s",
+            }
+        }
+    }
+
+    impl fmt::Display for Metric {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(self.name())
+        }
+    }
+
+    impl FromStr for Metric {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Metric::ALL
+                .iter()
+                .find(|metric| metric.name() == s)
+                .copied()
+                .ok_or_else(|| format!("unknown metric '{}'", s))
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+    pub enum RecordFormat {
+        Csv,
+        Ndjson,
+    }
+
+    impl RecordFormat {
+        pub fn extension(&self) -> &'static str {
+            match self {
+                RecordFormat::Csv => "csv",
+                RecordFormat::Ndjson => "ndjson",
+            }
+        }
+    }
+
+    // Métrique enregistrée avec son propre intervalle d'échantillonnage
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct MetricSchedule {
+        pub metric: Metric,
+        pub interval: Duration,
+    }
+
+    impl FromStr for MetricSchedule {
+        type Err = String;
+
+        // "cpu_usage" ou "cpu_usage:5s" (s, ms ou m)
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (name, interval) = match s.split_once(':') {
+                Some((name, interval)) => (name, parse_nonzero_duration(interval)?),
+                None => (s, Duration::from_secs(1)),
+            };
+            Ok(MetricSchedule {
+                metric: name.trim().parse()?,
+                interval,
+            })
+        }
+    }
+
+    pub fn parse_duration(s: &str) -> Result<Duration, String> {
+        let s = s.trim();
+        let (number, unit) = match s.find(|c: char| !c.is_ascii_digit() && c != '.') {
+            Some(i) => s.split_at(i),
+            None => (s, "s"),
+        };
+        let value: f64 = number
+            .parse()
+            .map_err(|_| format!("invalid duration '{}'", s))?;
+        let secs = match unit {
+            "ms" => value / 1000.0,
+            "s" => value,
+            "m" => value * 60.0,
+            "h" => value * 3600.0,
+            "d" => value * 86400.0,
+            _ => return Err(format!("invalid duration unit in '{}'", s)),
+        };
+        Duration::try_from_secs_f64(secs).map_err(|_| format!("duration '{}' is too long", s))
+    }
+
+    // Intervalles d'échantillonnage: une durée nulle ferait tourner la boucle à vide
+    pub fn parse_nonzero_duration(s: &str) -> Result<Duration, String> {
+        let duration = parse_duration(s)?;
+        if duration.is_zero() {
+            return Err(format!("duration '{}' must be greater than 0", s.trim()));
+        }
+        Ok(duration)
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct RecorderConfig {
+        pub directory: PathBuf,
+        pub format: RecordFormat,
+        pub metrics: Vec<MetricSchedule>,
+        // Rotation: nouveau fichier au-delà de cette taille, au plus `max_files` conservés
+        pub max_file_size: u64,
+        pub max_files: usize,
+    }
+
+    impl Default for RecorderConfig {
+        fn default() -> Self {
+            RecorderConfig {
+                directory: PathBuf::from("recordings"),
+                format: RecordFormat::Csv,
+                metrics: Metric::ALL
+                    .iter()
+                    .map(|&metric| MetricSchedule {
+                        metric,
+                        interval: Duration::from_secs(1),
+                    })
+                    .collect(),
+                max_file_size: 10 * 1024 * 1024,
+                max_files: 10,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize)]
+    pub struct Record {
+        pub timestamp: DateTime<Utc>,
+        pub metric: Metric,
+        pub value: f64,
+    }
+
+    // Lit les valeurs courantes et calcule les taux à partir des compteurs cumulés
+    pub struct MetricSampler {
+        system: System,
+        components: Components,
+        networks: Networks,
+        vmstat: Option<(VmStat, Instant)>,
+        network_totals: Option<(u64, u64, Instant)>,
+    }
+
+    impl MetricSampler {
+        pub fn new() -> MetricSampler {
+            let mut system = System::new();
+            system.refresh_cpu_usage();
+            MetricSampler {
+                system,
+                components: Components::new_with_refreshed_list(),
+                networks: Networks::new_with_refreshed_list(),
+                vmstat: None,
+                network_totals: None,
+            }
+        }
+
+        pub fn sample(&mut self, metrics: &[Metric]) -> Vec<(Metric, f64)> {
+            let wants = |list: &[Metric]| list.iter().any(|m| metrics.contains(m));
+            if metrics.contains(&Metric::CpuUsage) {
+                self.system.refresh_cpu_usage();
+            }
+            let meminfo = if wants(&[
+                Metric::RamUsed,
+                Metric::RamCache,
+                Metric::RamAvailable,
+                Metric::SwapUsed,
+            ]) {
+                MemInfo::read().unwrap_or_default()
+            } else {
+                MemInfo::default()
+            };
+            let vm_rates = if wants(&[
+                Metric::SwapIn,
+                Metric::SwapOut,
+                Metric::PageFaults,
+                Metric::MajorFaults,
+            ]) {
+                let vmstat = VmStat::read().unwrap_or_default();
+                let rates = self
+                    .vmstat
+                    .as_ref()
+                    .map(|(previous, at)| vmstat.rates_since(previous, at.elapsed()))
+                    .unwrap_or_default();
+                self.vmstat = Some((vmstat, Instant::now()));
+                rates
+            } else {
+                Default::default()
+            };
+            let (rx_rate, tx_rate) = if wants(&[Metric::NetworkRx, Metric::NetworkTx]) {
+                self.networks.refresh();
+                let rx: u64 = self.networks.values().map(|d| d.total_received()).sum();
+                let tx: u64 = self.networks.values().map(|d| d.total_transmitted()).sum();
+                let rates = match self.network_totals {
+                    Some((prev_rx, prev_tx, at)) => {
+                        let secs = at.elapsed().as_secs_f64().max(f64::EPSILON);
+                        (
+                            rx.saturating_sub(prev_rx) as f64 / secs,
+                            tx.saturating_sub(prev_tx) as f64 / secs,
+                        )
+                    }
+                    None => (0.0, 0.0),
+                };
+                self.network_totals = Some((rx, tx, Instant::now()));
+                rates
+            } else {
+                (0.0, 0.0)
+            };
+
+            metrics
+                .iter()
+                .map(|&metric| {
+                    let value = match metric {
+                        Metric::CpuUsage => self.system.global_cpu_info().cpu_usage() as f64,
+                        Metric::CpuTemperature => {
+                            self.components.refresh();
+                            self.components
+                                .iter()
+                                .find(|c| c.label().contains("coretemp Package id 0"))
+                                .map_or(0.0, |c| c.temperature() as f64)
+                        }
+                        Metric::FanSpeed => Cpu::get_all_fan_info()
+                            .ok()
+                            .and_then(|fans| fans.first().and_then(|fan| fan.rpm))
+                            .unwrap_or(0) as f64,
+                        Metric::RamUsed => meminfo.used() as f64,
+                        Metric::RamCache => meminfo.cache() as f64,
+                        Metric::RamAvailable => meminfo.mem_available as f64,
+                        Metric::SwapUsed => {
+                            meminfo.swap_total.saturating_sub(meminfo.swap_free) as f64
+                        }
+                        Metric::SwapIn => vm_rates.swap_in as f64,
+                        Metric::SwapOut => vm_rates.swap_out as f64,
+                        Metric::PageFaults => vm_rates.pgfault as f64,
+                        Metric::MajorFaults => vm_rates.pgmajfault as f64,
+                        Metric::NetworkRx => rx_rate,
+                        Metric::NetworkTx => tx_rate,
+                    };
+                    (metric, value)
+                })
+                .collect()
+        }
+    }
+
+    impl Default for MetricSampler {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Écrit les enregistrements dans des fichiers horodatés avec rotation par taille
+    pub struct RecordWriter {
+        config: RecorderConfig,
+        writer: Option<BufWriter<File>>,
+        pub current_path: Option<PathBuf>,
+        bytes_written: u64,
+    }
+
+    impl RecordWriter {
+        pub fn new(config: RecorderConfig) -> io::Result<RecordWriter> {
+            fs::create_dir_all(&config.directory)?;
+            Ok(RecordWriter {
+                config,
+                writer: None,
+                current_path: None,
+                bytes_written: 0,
+            })
+        }
+
+        pub fn write(&mut self, record: &Record) -> io::Result<()> {
+            if self.writer.is_none() || self.bytes_written >= self.config.max_file_size {
+                self.rotate()?;
+            }
+            let line = match self.config.format {
+                RecordFormat::Csv => format!(
+                    "{},{},{}\n",
+                    record.timestamp.to_rfc3339(),
+                    record.metric,
+                    record.value
+                ),
+                RecordFormat::Ndjson => {
+                    let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+                    line.push('\n');
+                    line
+                }
+            };
+            if let Some(writer) = self.writer.as_mut() {
+                writer.write_all(line.as_bytes())?;
+            }
+            self.bytes_written += line.len() as u64;
+            Ok(())
+        }
+
+        pub fn flush(&mut self) -> io::Result<()> {
+            match self.writer.as_mut() {
+                Some(writer) => writer.flush(),
+                None => Ok(()),
+            }
+        }
+
+        fn rotate(&mut self) -> io::Result<()> {
+            self.flush()?;
+            let extension = self.config.format.extension();
+            let timestamp = Utc::now().format("%Y%m%d-%H%M%S").to_string();
+            let mut path = self
+                .config
+                .directory
+                .join(format!("metrics-{}.{}", timestamp, extension));
+            // Deux rotations dans la même seconde
+            let mut suffix = 1;
+            while path.exists() {
+                path = self.config.directory.join(format!(
+                    "metrics-{}-{}.{}",
+                    timestamp, suffix, extension
+                ));
+                suffix += 1;
+            }
+            let mut writer = BufWriter::new(File::create(&path)?);
+            self.bytes_written = 0;
+            if self.config.format == RecordFormat::Csv {
+                writer.write_all(b"timestamp,metric,value\n")?;
+                self.bytes_written = 23;
+            }
+            self.writer = Some(writer);
+            self.current_path = Some(path);
+            remove_old_files(&self.config.directory, extension, self.config.max_files)
+        }
+    }
+
+    // Ordre de rotation d'un fichier "metrics-<date>-<heure>[-<n>].<ext>": l'horodatage puis
+    // le numéro ajouté quand plusieurs fichiers sont créés dans la même seconde
+    fn rotation_key(name: &str, extension: &str) -> Option<(String, u32)> {
+        let stem = name
+            .strip_prefix("metrics-")?
+            .strip_suffix(extension)?
+            .strip_suffix('.')?;
+        match stem.splitn(3, '-').collect::<Vec<&str>>()[..] {
+            [date, time] => Some((format!("{}-{}", date, time), 0)),
+            [date, time, suffix] => Some((format!("{}-{}", date, time), suffix.parse().ok()?)),
+            _ => None,
+        }
+    }
+
+    fn remove_old_files(directory: &Path, extension: &str, max_files: usize) -> io::Result<()> {
+        let mut files: Vec<((String, u32), PathBuf)> = fs::read_dir(directory)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter_map(|path| {
+                let name = path.file_name().and_then(|n| n.to_str())?;
+                Some((rotation_key(name, extension)?, path))
+            })
+            .collect();
+        files.sort();
+        if files.len() > max_files.max(1) {
+            for (_, path) in &files[..files.len() - max_files.max(1)] {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Enregistreur lancé dans un thread; `stop` termine proprement l'écriture
+    pub struct Recorder {
+        running: Arc<AtomicBool>,
+        pub records_written: Arc<AtomicU64>,
+        pub current_path: Arc<Mutex<Option<PathBuf>>>,
+        pub error: Arc<Mutex<Option<String>>>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl Recorder {
+        pub fn start(config: RecorderConfig) -> io::Result<Recorder> {
+            let mut writer = RecordWriter::new(config.clone())?;
+            let running = Arc::new(AtomicBool::new(true));
+            let records_written = Arc::new(AtomicU64::new(0));
+            let current_path = Arc::new(Mutex::new(None));
+            let error = Arc::new(Mutex::new(None));
+
+            let mut recorder = Recorder {
+                running: running.clone(),
+                records_written: records_written.clone(),
+                current_path: current_path.clone(),
+                error: error.clone(),
+                handle: None,
+            };
+
+            let handle = thread::spawn(move || {
+                let mut sampler = MetricSampler::new();
+                let mut last_sample: HashMap<Metric, Instant> = HashMap::new();
+                let tick = config
+                    .metrics
+                    .iter()
+                    .map(|schedule| schedule.interval)
+                    .min()
+                    .unwrap_or(Duration::from_secs(1))
+                    .min(Duration::from_millis(100));
+                while running.load(Ordering::Relaxed) {
+                    let due: Vec<Metric> = config
+                        .metrics
+                        .iter()
+                        .filter(|schedule| {
+                            last_sample
+                                .get(&schedule.metric)
+                                .is_none_or(|at| at.elapsed() >= schedule.interval)
+                        })
+                        .map(|schedule| schedule.metric)
+                        .collect();
+                    if !due.is_empty() {
+                        let timestamp = Utc::now();
+                        let result =
+                            sampler
+                                .sample(&due)
+                                .into_iter()
+                                .try_for_each(|(metric, value)| {
+                                    last_sample.insert(metric, Instant::now());
+                                    writer.write(&Record {
+                                        timestamp,
+                                        metric,
+                                        value,
+                                    })
+                                });
+                        let result = result.and_then(|_| writer.flush());
+                        match result {
+                            Ok(()) => {
+                                records_written.fetch_add(due.len() as u64, Ordering::Relaxed);
+                                *current_path.lock().unwrap() = writer.current_path.clone();
+                            }
+                            Err(e) => {
+                                *error.lock().unwrap() = Some(e.to_string());
+                                break;
+                            }
+                        }
+                    }
+                    thread::sleep(tick);
+                }
+                let _ = writer.flush();
+                running.store(false, Ordering::Relaxed);
+            });
+
+            recorder.handle = Some(handle);
+            Ok(recorder)
+        }
+
+        pub fn is_running(&self) -> bool {
+            self.running.load(Ordering::Relaxed)
+        }
+
+        pub fn stop(&mut self) {
+            self.running.store(false, Ordering::Relaxed);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    impl Drop for Recorder {
+        fn drop(&mut self) {
+            self.stop();
+        }
+    }
+
+    // État de la fenêtre "Recorder" de l'interface graphique
+    pub struct RecorderPanel {
+        pub directory: String,
+        pub format: RecordFormat,
+        // (métrique, activée, intervalle en secondes)
+        pub metrics: Vec<(Metric, bool, f32)>,
+        pub max_file_size_mb: i32,
+        pub max_files: i32,
+        pub recorder: Option<Recorder>,
+        pub status: String,
+    }
+
+    impl RecorderPanel {
+        pub fn new() -> RecorderPanel {
+            let config = RecorderConfig::default();
+            RecorderPanel {
+                directory: config.directory.to_string_lossy().to_string(),
+                format: config.format,
+                metrics: Metric::ALL.iter().map(|&m| (m, true, 1.0)).collect(),
+                max_file_size_mb: (config.max_file_size / (1024 * 1024)) as i32,
+                max_files: config.max_files as i32,
+                recorder: None,
+                status: String::new(),
+            }
+        }
+
+        pub fn config(&self) -> RecorderConfig {
+            RecorderConfig {
+                directory: PathBuf::from(&self.directory),
+                format: self.format,
+                metrics: self
+                    .metrics
+                    .iter()
+                    .filter(|(_, enabled, _)| *enabled)
+                    .map(|&(metric, _, secs)| MetricSchedule {
+                        metric,
+                        interval: Duration::from_secs_f32(secs.max(0.1)),
+                    })
+                    .collect(),
+                max_file_size: self.max_file_size_mb.max(1) as u64 * 1024 * 1024,
+                max_files: self.max_files.max(1) as usize,
+            }
+        }
+
+        pub fn draw(&mut self, ui: &Ui) {
+            let running = self.recorder.as_ref().is_some_and(|r| r.is_running());
+            if running {
+                if ui.button("Stop recording") {
+                    if let Some(mut recorder) = self.recorder.take() {
+                        recorder.stop();
+                        self.status = format!(
+                            "Stopped after {} records",
+                            recorder.records_written.load(Ordering::Relaxed)
+                        );
+                    }
+                }
+            } else if ui.button("Start recording") {
+                self.status = match Recorder::start(self.config()) {
+                    Ok(recorder) => {
+                        self.recorder = Some(recorder);
+                        "Recording...".to_string()
+                    }
+                    Err(e) => format!("Error starting recorder: {}", e),
+                };
+            }
+            ui.same_line();
+            ui.text(&self.status);
+
+            if let Some(recorder) = &self.recorder {
+                if let Some(path) = recorder.current_path.lock().unwrap().as_ref() {
+                    ui.text(format!(
+                        "File: {} ({} records)",
+                        path.display(),
+                        recorder.records_written.load(Ordering::Relaxed)
+                    ));
+                }
+                if let Some(error) = recorder.error.lock().unwrap().as_ref() {
+                    ui.text_colored([1.0, 0.3, 0.3, 1.0], format!("Error: {}", error));
+                }
+            }
+            ui.separator();
+
+            // Les réglages ne sont modifiables qu'à l'arrêt
+            let _disabled = ui.begin_disabled(running);
+            ui.input_text("Directory", &mut self.directory).build();
+            ui.radio_button("CSV", &mut self.format, RecordFormat::Csv);
+            ui.same_line();
+            ui.radio_button("NDJSON", &mut self.format, RecordFormat::Ndjson);
+            ui.input_int("Max file size (Mo)", &mut self.max_file_size_mb)
+                .build();
+            ui.input_int("Max files", &mut self.max_files).build();
+            ui.separator();
+            for (metric, enabled, secs) in self.metrics.iter_mut() {
+                ui.checkbox(metric.name(), enabled);
+                ui.same_line_with_pos(200.0);
+                let _id = ui.push_id(metric.name());
+                ui.set_next_item_width(150.0);
+                ui.input_float("Interval (s)", secs).step(1.0).build();
+            }
+        }
+    }
+
+    impl Default for RecorderPanel {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
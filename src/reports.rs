@@ -0,0 +1,114 @@
+pub mod report {
+    use std::io::{self, Write};
+
+    use crate::{
+        convert_bytes_to_any, get_process_info, Computer, Cpu, Memory, Network, Section,
+    };
+
+    pub fn write_report<W: Write>(out: &mut W, sections: &[Section], top: usize) -> io::Result<()> {
+        for section in sections {
+            match section {
+                Section::System => write_system(out)?,
+                Section::Cpu => write_cpu(out)?,
+                Section::Memory => write_memory(out)?,
+                Section::Network => write_network(out)?,
+                Section::Processes => write_processes(out, top)?,
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    fn write_system<W: Write>(out: &mut W) -> io::Result<()> {
+        let computer = Computer::new();
+        writeln!(out, "== System ==")?;
+        write!(out, "{}", computer.report())
+    }
+
+    fn write_cpu<W: Write>(out: &mut W) -> io::Result<()> {
+        writeln!(out, "== CPU ==")?;
+        writeln!(out, "CPU Usage: {:.2}%", Cpu::measure_cpu_usage())?;
+        writeln!(out, "CPU Temperature: {:.2}°C", Cpu::get_cpu_temperatures())?;
+        match Cpu::get_all_fan_info() {
+            Ok(fans) if !fans.is_empty() => {
+                for (i, fan) in fans.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "Fan {}: {} RPM ({})",
+                        i + 1,
+                        fan.rpm.unwrap_or(0),
+                        fan.state.as_deref().unwrap_or("N/A")
+                    )?;
+                }
+            }
+            _ => writeln!(out, "Fans: N/A")?,
+        }
+        Ok(())
+    }
+
+    fn write_memory<W: Write>(out: &mut W) -> io::Result<()> {
+        let mut memory = Memory::new();
+        memory.get_memory();
+        writeln!(out, "== Memory ==")?;
+        writeln!(
+            out,
+            "RAM: {} used / {} total ({} free)",
+            convert_bytes_to_any(memory.ram.used_ram),
+            convert_bytes_to_any(memory.ram.total_ram),
+            convert_bytes_to_any(memory.ram.free_ram)
+        )?;
+        writeln!(
+            out,
+            "Swap: {} used / {} total ({} free)",
+            convert_bytes_to_any(memory.swap.used_swap),
+            convert_bytes_to_any(memory.swap.total_swap),
+            convert_bytes_to_any(memory.swap.free_swap)
+        )?;
+        writeln!(
+            out,
+            "Storage: {} used / {} total ({} free)",
+            convert_bytes_to_any(memory.storage.used_disk),
+            convert_bytes_to_any(memory.storage.total_disk),
+            convert_bytes_to_any(memory.storage.free_disk)
+        )
+    }
+
+    fn write_network<W: Write>(out: &mut W) -> io::Result<()> {
+        let mut network = Network::new();
+        network.initialize();
+        writeln!(out, "== Network ==")?;
+        writeln!(out, "{:<16} {:<16} {:>12} {:>12}", "Interface", "IP", "RX", "TX")?;
+        for interface in &network.interfaces {
+            writeln!(
+                out,
+                "{:<16} {:<16} {:>12} {:>12}",
+                interface.name,
+                interface.ip.to_string(),
+                convert_bytes_to_any(interface.total_received),
+                convert_bytes_to_any(interface.total_transmitted)
+            )?;
+        }
+        Ok(())
+    }
+
+    fn write_processes<W: Write>(out: &mut W, top: usize) -> io::Result<()> {
+        let mut processes = get_process_info();
+        processes.sort_by(|a, b| b.memory_usage.total_cmp(&a.memory_usage));
+        writeln!(out, "== Processes ==")?;
+        writeln!(out, "Total Processes: {}", processes.len())?;
+        writeln!(
+            out,
+            "{:>8} {:<24} {:<6} {:>10} {:>10}",
+            "PID", "Name", "State", "CPU", "Memory"
+        )?;
+        let count = if top == 0 { processes.len() } else { top };
+        for process in processes.iter().take(count) {
+            writeln!(
+                out,
+                "{:>8} {:<24} {:<6} {:>9.2}% {:>9.2}%",
+                process.pid, process.name, process.state, process.cpu_usage, process.memory_usage
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,516 @@
+pub mod service {
+    use std::{
+        cmp::Ordering,
+        collections::HashMap,
+        io,
+        path::{Path, PathBuf},
+        process::Command,
+        sync::{
+            atomic::{AtomicBool, Ordering as AtomicOrdering},
+            mpsc::{self, Sender},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::Instant,
+    };
+
+    use imgui::{StyleColor, Ui};
+
+    use crate::{cgroup_hierarchy, CgroupStats, Palette, ProcessInfo};
+
+    pub const DEFAULT_SYSTEMCTL: &str = "systemctl";
+
+    // Propriétés demandées à `systemctl show`
+    const PROPERTIES: &str = "Id,Description,LoadState,ActiveState,SubState,NRestarts,MainPID";
+
+    // Cgroup de l'unité systemd d'un processus: le service ou scope le plus profond,
+    // sinon la slice (ex: /user.slice/user-1000.slice/session-2.scope)
+    pub fn unit_cgroup(path: &str) -> Option<String> {
+        let segments: Vec<&str> = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let index = segments
+            .iter()
+            .rposition(|segment| segment.ends_with(".service") || segment.ends_with(".scope"))
+            .or_else(|| {
+                segments
+                    .iter()
+                    .rposition(|segment| segment.ends_with(".slice"))
+            })?;
+        Some(format!("/{}", segments[..=index].join("/")))
+    }
+
+    // Unité systemd d'un processus d'après son cgroup (dernier segment de unit_cgroup)
+    pub fn unit_from_cgroup(path: &str) -> Option<String> {
+        unit_cgroup(path).and_then(|cgroup| cgroup.rsplit('/').next().map(str::to_string))
+    }
+
+    // Unité du gestionnaire de l'utilisateur (sous user@<uid>.service), inconnue de
+    // l'instance système de systemctl
+    pub fn is_user_unit(path: &str, unit: &str) -> bool {
+        let mut segments = path
+            .split('/')
+            .skip_while(|segment| !segment.starts_with("user@"));
+        segments.next().is_some_and(|manager| manager != unit)
+    }
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct UnitState {
+        pub id: String,
+        pub description: String,
+        pub load_state: String,
+        pub active_state: String,
+        pub sub_state: String,
+        // NRestarts: redémarrages automatiques depuis le chargement (services seulement)
+        pub restarts: Option<u32>,
+        pub main_pid: Option<usize>,
+    }
+
+    impl UnitState {
+        pub fn label(&self) -> String {
+            if self.sub_state.is_empty() || self.sub_state == self.active_state {
+                self.active_state.clone()
+            } else {
+                format!("{} ({})", self.active_state, self.sub_state)
+            }
+        }
+
+        pub fn color(&self, palette: &Palette, disabled: [f32; 4]) -> [f32; 4] {
+            match self.active_state.as_str() {
+                "active" => palette.good.0,
+                "failed" => palette.critical.0,
+                "activating" | "deactivating" | "reloading" => palette.warning.0,
+                _ => disabled,
+            }
+        }
+    }
+
+    // Sortie de `systemctl show`: blocs "Clé=valeur" séparés par une ligne vide
+    pub fn parse_systemctl_show(content: &str) -> Vec<UnitState> {
+        let mut states = Vec::new();
+        let mut current: Option<UnitState> = None;
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                states.extend(current.take());
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let state = current.get_or_insert_with(UnitState::default);
+            match key {
+                "Id" => state.id = value.to_string(),
+                "Description" => state.description = value.to_string(),
+                "LoadState" => state.load_state = value.to_string(),
+                "ActiveState" => state.active_state = value.to_string(),
+                "SubState" => state.sub_state = value.to_string(),
+                "NRestarts" => state.restarts = value.parse().ok(),
+                "MainPID" => state.main_pid = value.parse().ok().filter(|pid| *pid > 0),
+                _ => {}
+            }
+        }
+        states.extend(current);
+        states.retain(|state| !state.id.is_empty());
+        states
+    }
+
+    pub fn query_units(systemctl: &Path, units: &[String]) -> io::Result<Vec<UnitState>> {
+        if units.is_empty() {
+            return Ok(Vec::new());
+        }
+        let output = Command::new(systemctl)
+            .arg("show")
+            .arg(format!("--property={}", PROPERTIES))
+            .arg("--")
+            .args(units)
+            .output()?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!(
+                "{} show: {}",
+                systemctl.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(parse_systemctl_show(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    // Processus regroupés par unité
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ServiceGroup {
+        pub unit: String,
+        pub user: bool,
+        pub pids: Vec<usize>,
+        // Utilisation CPU du cgroup de l'unité (inconnue hors système local)
+        pub cpu_usage: Option<f32>,
+        pub memory_usage: f32,
+        pub state: Option<UnitState>,
+    }
+
+    pub fn group_services(
+        processes: &[ProcessInfo],
+        states: &HashMap<String, UnitState>,
+        cpu: &HashMap<String, f32>,
+    ) -> Vec<ServiceGroup> {
+        let mut groups: HashMap<String, ServiceGroup> = HashMap::new();
+        for process in processes {
+            let Some(unit) = unit_from_cgroup(&process.cgroup) else {
+                continue;
+            };
+            let group = groups.entry(unit.clone()).or_insert_with(|| ServiceGroup {
+                user: is_user_unit(&process.cgroup, &unit),
+                state: states.get(&unit).cloned(),
+                cpu_usage: cpu.get(&unit).copied(),
+                unit,
+                pids: Vec::new(),
+                memory_usage: 0.0,
+            });
+            group.pids.push(process.pid);
+            group.memory_usage += process.memory_usage;
+        }
+        groups.into_values().collect()
+    }
+
+    type QueryResult = Result<Vec<UnitState>, String>;
+
+    // Dernier état connu des unités, relu avec systemctl à intervalle régulier dans un
+    // thread dédié pour ne pas bloquer l'affichage
+    pub struct ServiceMonitor {
+        pub systemctl: PathBuf,
+        // Racine cgroup v2 où est lu le cpu.stat de chaque unité
+        pub cgroup_root: PathBuf,
+        pub states: HashMap<String, UnitState>,
+        // Utilisation CPU de chaque unité en %, par rapport à la lecture précédente
+        pub cpu: HashMap<String, f32>,
+        pub error: Option<String>,
+        pub last_refresh: Option<Instant>,
+        previous_usage: HashMap<String, (u64, Instant)>,
+        busy: Arc<AtomicBool>,
+        result: Arc<Mutex<Option<QueryResult>>>,
+        sender: Option<Sender<(PathBuf, Vec<String>)>>,
+        worker: Option<JoinHandle<()>>,
+    }
+
+    impl ServiceMonitor {
+        pub fn new<P: Into<PathBuf>, Q: Into<PathBuf>>(
+            systemctl: P,
+            cgroup_root: Q,
+        ) -> ServiceMonitor {
+            let busy = Arc::new(AtomicBool::new(false));
+            let result = Arc::new(Mutex::new(None));
+            let (sender, receiver) = mpsc::channel::<(PathBuf, Vec<String>)>();
+            let worker = {
+                let busy = busy.clone();
+                let result = result.clone();
+                thread::spawn(move || {
+                    for (systemctl, units) in receiver {
+                        let states = query_units(&systemctl, &units).map_err(|e| e.to_string());
+                        *result.lock().unwrap() = Some(states);
+                        busy.store(false, AtomicOrdering::Relaxed);
+                    }
+                })
+            };
+            ServiceMonitor {
+                systemctl: systemctl.into(),
+                cgroup_root: cgroup_root.into(),
+                states: HashMap::new(),
+                cpu: HashMap::new(),
+                error: None,
+                last_refresh: None,
+                previous_usage: HashMap::new(),
+                busy,
+                result,
+                sender: Some(sender),
+                worker: Some(worker),
+            }
+        }
+
+        // Reprend le résultat de la dernière requête terminée (à appeler à chaque image)
+        pub fn poll(&mut self) {
+            let Some(result) = self.result.lock().unwrap().take() else {
+                return;
+            };
+            match result {
+                Ok(states) => {
+                    self.states = states
+                        .into_iter()
+                        .map(|state| (state.id.clone(), state))
+                        .collect();
+                    self.error = None;
+                }
+                Err(e) => self.error = Some(e),
+            }
+        }
+
+        // Utilisation CPU des unités: écart de usage_usec (cpu.stat) de leur cgroup
+        // entre deux lectures, rapporté au temps écoulé
+        pub fn sample_cpu(&mut self, processes: &[ProcessInfo]) {
+            let hierarchy = cgroup_hierarchy(&self.cgroup_root).ok();
+            let now = Instant::now();
+            let mut cgroups: Vec<String> = processes
+                .iter()
+                .filter_map(|process| unit_cgroup(&process.cgroup))
+                .collect();
+            cgroups.sort();
+            cgroups.dedup();
+            let mut current = HashMap::new();
+            self.cpu.clear();
+            for cgroup in cgroups {
+                let Some(usage) = hierarchy.as_ref().and_then(|hierarchy| {
+                    CgroupStats::read(&hierarchy.join(cgroup.trim_start_matches('/')))
+                        .cpu_usage_usec
+                }) else {
+                    continue;
+                };
+                let Some(unit) = unit_from_cgroup(&cgroup) else {
+                    continue;
+                };
+                if let Some((before, time)) = self.previous_usage.get(&cgroup) {
+                    let elapsed = now.duration_since(*time).as_secs_f64();
+                    if elapsed > 0.0 {
+                        let percent = usage.saturating_sub(*before) as f64 / elapsed / 10_000.0;
+                        self.cpu.insert(unit, percent as f32);
+                    }
+                }
+                current.insert(cgroup, (usage, now));
+            }
+            self.previous_usage = current;
+        }
+
+        // Demande une nouvelle lecture, sauf si la précédente n'est pas terminée.
+        // Les unités utilisateur ne sont pas demandées à l'instance système
+        pub fn refresh(&mut self, processes: &[ProcessInfo]) {
+            self.sample_cpu(processes);
+            // Requête systemctl encore en cours: son résultat sera repris par poll()
+            if self.busy.load(AtomicOrdering::Relaxed) {
+                self.last_refresh = Some(Instant::now());
+                return;
+            }
+            let mut units: Vec<String> = processes
+                .iter()
+                .filter_map(|process| {
+                    unit_from_cgroup(&process.cgroup)
+                        .filter(|unit| !is_user_unit(&process.cgroup, unit))
+                })
+                .collect();
+            units.sort();
+            units.dedup();
+            if let Some(sender) = &self.sender {
+                self.busy.store(true, AtomicOrdering::Relaxed);
+                if sender.send((self.systemctl.clone(), units)).is_err() {
+                    self.busy.store(false, AtomicOrdering::Relaxed);
+                }
+            }
+            self.last_refresh = Some(Instant::now());
+        }
+    }
+
+    impl Drop for ServiceMonitor {
+        fn drop(&mut self) {
+            self.sender.take();
+            if let Some(worker) = self.worker.take() {
+                let _ = worker.join();
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum ServiceColumn {
+        #[default]
+        Unit,
+        State,
+        Processes,
+        Cpu,
+        Memory,
+        Restarts,
+    }
+
+    impl ServiceColumn {
+        pub const ALL: [ServiceColumn; 6] = [
+            ServiceColumn::Unit,
+            ServiceColumn::State,
+            ServiceColumn::Processes,
+            ServiceColumn::Cpu,
+            ServiceColumn::Memory,
+            ServiceColumn::Restarts,
+        ];
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                ServiceColumn::Unit => "Unit",
+                ServiceColumn::State => "State",
+                ServiceColumn::Processes => "Processes",
+                ServiceColumn::Cpu => "CPU Usage",
+                ServiceColumn::Memory => "Memory Usage",
+                ServiceColumn::Restarts => "Restarts",
+            }
+        }
+
+        fn compare(&self, a: &ServiceGroup, b: &ServiceGroup) -> Ordering {
+            let state = |group: &ServiceGroup| {
+                group
+                    .state
+                    .as_ref()
+                    .map(|state| state.active_state.clone())
+                    .unwrap_or_default()
+            };
+            let restarts =
+                |group: &ServiceGroup| group.state.as_ref().and_then(|state| state.restarts);
+            match self {
+                ServiceColumn::Unit => a.unit.cmp(&b.unit),
+                ServiceColumn::State => state(a).cmp(&state(b)),
+                ServiceColumn::Processes => a.pids.len().cmp(&b.pids.len()),
+                ServiceColumn::Cpu => a
+                    .cpu_usage
+                    .unwrap_or(-1.0)
+                    .total_cmp(&b.cpu_usage.unwrap_or(-1.0)),
+                ServiceColumn::Memory => a.memory_usage.total_cmp(&b.memory_usage),
+                ServiceColumn::Restarts => restarts(a).cmp(&restarts(b)),
+            }
+        }
+    }
+
+    // Recherche, tri et unité dépliée de la fenêtre "Services"
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct ServiceView {
+        pub search: String,
+        pub sort_column: ServiceColumn,
+        pub sort_descending: bool,
+        pub selected: Option<String>,
+    }
+
+    impl ServiceView {
+        pub fn new() -> ServiceView {
+            Self::default()
+        }
+
+        pub fn sort_by(&mut self, column: ServiceColumn) {
+            if self.sort_column == column {
+                self.sort_descending = !self.sort_descending;
+            } else {
+                self.sort_column = column;
+                self.sort_descending = false;
+            }
+        }
+    }
+
+    pub fn draw_services(
+        ui: &Ui,
+        groups: &[ServiceGroup],
+        processes: &[ProcessInfo],
+        view: &mut ServiceView,
+        error: Option<&str>,
+        palette: &Palette,
+    ) {
+        if let Some(error) = error {
+            ui.text_colored(
+                [1.0, 0.3, 0.3, 1.0],
+                format!("Unit states unavailable: {}", error),
+            );
+        }
+        let failed = groups
+            .iter()
+            .filter(|group| {
+                group
+                    .state
+                    .as_ref()
+                    .is_some_and(|state| state.active_state == "failed")
+            })
+            .count();
+        ui.text(format!("Units: {}   Failed: {}", groups.len(), failed));
+        ui.input_text("Search", &mut view.search).build();
+        ui.separator();
+
+        let disabled = ui.style_color(StyleColor::TextDisabled);
+        ui.columns(ServiceColumn::ALL.len() as i32, "ServiceColumns", true);
+        for column in ServiceColumn::ALL {
+            let mut header = column.label().to_string();
+            if column == view.sort_column {
+                header.push_str(if view.sort_descending { " v" } else { " ^" });
+            }
+            if ui.selectable(&header) {
+                view.sort_by(column);
+            }
+            ui.next_column();
+        }
+        ui.separator();
+
+        let mut visible: Vec<&ServiceGroup> = groups
+            .iter()
+            .filter(|group| group.unit.contains(&view.search))
+            .collect();
+        visible.sort_by(|a, b| {
+            let ordering = view.sort_column.compare(a, b);
+            if view.sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        for group in visible {
+            let selected = view.selected.as_deref() == Some(group.unit.as_str());
+            if ui.selectable_config(&group.unit).selected(selected).build() {
+                view.selected = if selected {
+                    None
+                } else {
+                    Some(group.unit.clone())
+                };
+            }
+            if let Some(description) = group
+                .state
+                .as_ref()
+                .map(|state| &state.description)
+                .filter(|description| !description.is_empty())
+            {
+                if ui.is_item_hovered() {
+                    ui.tooltip_text(description);
+                }
+            }
+            ui.next_column();
+            match (&group.state, group.user) {
+                (Some(state), _) => ui.text_colored(state.color(palette, disabled), state.label()),
+                (None, true) => ui.text_disabled("user unit"),
+                (None, false) => ui.text_disabled("-"),
+            }
+            ui.next_column();
+            ui.text(group.pids.len().to_string());
+            ui.next_column();
+            match group.cpu_usage {
+                Some(cpu_usage) => ui.text(format!("{:.2}%", cpu_usage)),
+                None => ui.text_disabled("-"),
+            }
+            ui.next_column();
+            ui.text(format!("{:.2}%", group.memory_usage));
+            ui.next_column();
+            match group.state.as_ref().and_then(|state| state.restarts) {
+                Some(restarts) => ui.text(restarts.to_string()),
+                None => ui.text_disabled("-"),
+            }
+            ui.next_column();
+            if selected {
+                let main_pid = group.state.as_ref().and_then(|state| state.main_pid);
+                for pid in &group.pids {
+                    let name = processes
+                        .iter()
+                        .find(|process| process.pid == *pid)
+                        .map(|process| process.name.as_str())
+                        .unwrap_or_default();
+                    let main = if main_pid == Some(*pid) {
+                        " (main)"
+                    } else {
+                        ""
+                    };
+                    ui.text_disabled(format!("  {} {}{}", pid, name, main));
+                    for _ in ServiceColumn::ALL {
+                        ui.next_column();
+                    }
+                }
+            }
+            ui.separator();
+        }
+        ui.columns(1, "", false);
+    }
+}
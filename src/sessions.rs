@@ -0,0 +1,347 @@
+pub mod session {
+    use std::{
+        fs::File,
+        io::{self, BufReader, BufWriter, ErrorKind, Read, Write},
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
+    };
+
+    use imgui::Ui;
+
+    use crate::{format_timestamp, Sample, Snapshot, SnapshotCollector, VmStat};
+
+    // En-tête des fichiers de session (le dernier octet est la version du format)
+    pub const SESSION_MAGIC: &[u8; 8] = b"SMSESS\0\x04";
+
+    // Taille maximale d'un enregistrement accepté à la lecture
+    const MAX_RECORD_SIZE: usize = 64 * 1024 * 1024;
+
+    // Enregistrement: taille du snapshot (u32 little-endian) puis le snapshot encodé avec bincode
+    pub fn encode_record(snapshot: &Snapshot) -> io::Result<Vec<u8>> {
+        let bytes = bincode::serialize(snapshot).map_err(io::Error::other)?;
+        let mut record = Vec::with_capacity(bytes.len() + 4);
+        record.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        record.extend_from_slice(&bytes);
+        Ok(record)
+    }
+
+    // Lit un enregistrement: None à la fin du flux, UnexpectedEof s'il est tronqué
+    pub fn read_record<R: Read>(reader: &mut R) -> io::Result<Option<Snapshot>> {
+        let mut length = [0u8; 4];
+        match reader.read(&mut length[..1])? {
+            0 => return Ok(None),
+            _ => reader.read_exact(&mut length[1..])?,
+        }
+        let length = u32::from_le_bytes(length) as usize;
+        if length > MAX_RECORD_SIZE {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("record too large ({} bytes)", length),
+            ));
+        }
+        let mut bytes = vec![0u8; length];
+        reader.read_exact(&mut bytes)?;
+        bincode::deserialize(&bytes)
+            .map(Some)
+            .map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+    }
+
+    // Format: en-tête puis un enregistrement par snapshot
+    pub struct SessionWriter {
+        writer: BufWriter<File>,
+    }
+
+    impl SessionWriter {
+        pub fn create<P: AsRef<Path>>(path: P) -> io::Result<SessionWriter> {
+            let mut writer = BufWriter::new(File::create(path)?);
+            writer.write_all(SESSION_MAGIC)?;
+            Ok(SessionWriter { writer })
+        }
+
+        pub fn write(&mut self, snapshot: &Snapshot) -> io::Result<()> {
+            self.writer.write_all(&encode_record(snapshot)?)?;
+            // Chaque snapshot est écrit immédiatement pour survivre à un arrêt brutal
+            self.writer.flush()
+        }
+    }
+
+    // Lit tous les snapshots d'une session; un dernier enregistrement tronqué est ignoré
+    pub fn read_session<P: AsRef<Path>>(path: P) -> io::Result<Vec<Snapshot>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != SESSION_MAGIC {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                "not a system_monitor session file",
+            ));
+        }
+        let mut frames = Vec::new();
+        loop {
+            match read_record(&mut reader) {
+                Ok(Some(snapshot)) => frames.push(snapshot),
+                Ok(None) => break,
+                Err(e) if matches!(e.kind(), ErrorKind::UnexpectedEof | ErrorKind::InvalidData) => {
+                    break
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(frames)
+    }
+
+    // Enregistre un snapshot complet à intervalle régulier dans un thread dédié
+    pub struct SessionRecorder {
+        running: Arc<AtomicBool>,
+        pub frames_written: Arc<AtomicU64>,
+        pub error: Arc<Mutex<Option<String>>>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl SessionRecorder {
+        pub fn start<P: AsRef<Path>>(path: P, interval: Duration) -> io::Result<SessionRecorder> {
+            let mut writer = SessionWriter::create(path)?;
+            let running = Arc::new(AtomicBool::new(true));
+            let frames_written = Arc::new(AtomicU64::new(0));
+            let error = Arc::new(Mutex::new(None));
+            let mut recorder = SessionRecorder {
+                running: running.clone(),
+                frames_written: frames_written.clone(),
+                error: error.clone(),
+                handle: None,
+            };
+            recorder.handle = Some(thread::spawn(move || {
+                let mut collector = SnapshotCollector::new();
+                // Laisser sysinfo mesurer l'utilisation CPU sur un premier intervalle
+                thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+                let mut next = Instant::now();
+                while running.load(Ordering::Relaxed) {
+                    if let Err(e) = writer.write(&collector.collect()) {
+                        *error.lock().unwrap() = Some(e.to_string());
+                        break;
+                    }
+                    frames_written.fetch_add(1, Ordering::Relaxed);
+                    // En cas de retard, on repart de maintenant au lieu de rattraper
+                    next = (next + interval).max(Instant::now());
+                    while running.load(Ordering::Relaxed) && Instant::now() < next {
+                        thread::sleep(Duration::from_millis(50).min(interval));
+                    }
+                }
+                running.store(false, Ordering::Relaxed);
+            }));
+            Ok(recorder)
+        }
+
+        pub fn is_running(&self) -> bool {
+            self.running.load(Ordering::Relaxed)
+        }
+
+        pub fn stop(&mut self) {
+            self.running.store(false, Ordering::Relaxed);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    impl Drop for SessionRecorder {
+        fn drop(&mut self) {
+            self.stop();
+        }
+    }
+
+    // Relecture d'une session: position courante, lecture/pause et vitesse
+    pub struct SessionPlayer {
+        // Origine affichée au-dessus des contrôles, ex: "Session: session.smsession"
+        pub title: String,
+        pub frames: Vec<Snapshot>,
+        pub position: usize,
+        pub playing: bool,
+        pub speed: f32,
+        // Flux en direct (agent distant): la lecture attend les snapshots suivants
+        pub live: bool,
+        last_advance: Instant,
+    }
+
+    impl SessionPlayer {
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<SessionPlayer> {
+            let frames = read_session(&path)?;
+            if frames.is_empty() {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    "session contains no snapshot",
+                ));
+            }
+            Ok(SessionPlayer::from_frames(
+                format!("Session: {}", path.as_ref().display()),
+                frames,
+            ))
+        }
+
+        // `frames` ne doit pas être vide
+        pub fn from_frames(title: String, frames: Vec<Snapshot>) -> SessionPlayer {
+            SessionPlayer {
+                title,
+                frames,
+                position: 0,
+                playing: true,
+                speed: 1.0,
+                live: false,
+                last_advance: Instant::now(),
+            }
+        }
+
+        // Ajoute des snapshots reçus en direct en gardant les `keep` derniers; en lecture,
+        // la position suit le plus récent si elle y était. Renvoie vrai si elle a changé.
+        pub fn append(&mut self, frames: Vec<Snapshot>, keep: usize) -> bool {
+            let follow = self.playing && self.position + 1 >= self.frames.len();
+            let last = self.frames.last().map(|frame| frame.timestamp);
+            let before = self.frames.len();
+            self.frames.extend(
+                frames
+                    .into_iter()
+                    .filter(|frame| last.is_none_or(|last| frame.timestamp > last)),
+            );
+            let excess = self.frames.len().saturating_sub(keep.max(1));
+            self.frames.drain(..excess);
+            if follow {
+                self.position = self.frames.len() - 1;
+                self.frames.len() + excess > before
+            } else {
+                self.position = self.position.saturating_sub(excess);
+                false
+            }
+        }
+
+        pub fn current(&self) -> &Snapshot {
+            &self.frames[self.position]
+        }
+
+        // Avance d'un snapshot quand l'écart enregistré (divisé par la vitesse) est écoulé.
+        // Renvoie vrai si la position a changé.
+        pub fn advance(&mut self) -> bool {
+            if !self.playing {
+                self.last_advance = Instant::now();
+                return false;
+            }
+            if self.position + 1 >= self.frames.len() {
+                if !self.live {
+                    self.playing = false;
+                }
+                return false;
+            }
+            let gap = (self.frames[self.position + 1].timestamp
+                - self.frames[self.position].timestamp)
+                .to_std()
+                .unwrap_or_default()
+                // Ne pas attendre des heures entre deux enregistrements distincts
+                .min(Duration::from_secs(5));
+            if self.last_advance.elapsed().as_secs_f32() * self.speed >= gap.as_secs_f32() {
+                self.position += 1;
+                self.last_advance = Instant::now();
+                return true;
+            }
+            false
+        }
+
+        pub fn seek(&mut self, position: usize) {
+            self.position = position.min(self.frames.len() - 1);
+            self.last_advance = Instant::now();
+        }
+
+        // Valeurs des `len` derniers snapshots jusqu'à la position courante
+        pub fn series<F: Fn(&Snapshot) -> Option<f32>>(&self, len: usize, value: F) -> Vec<Sample> {
+            let start = (self.position + 1).saturating_sub(len);
+            self.frames[start..=self.position]
+                .iter()
+                .map(|frame| Sample {
+                    timestamp: frame.timestamp,
+                    value: value(frame).unwrap_or(0.0),
+                })
+                .collect()
+        }
+
+        // Taux par seconde d'un compteur de /proc/vmstat entre snapshots consécutifs
+        pub fn rate_series<F: Fn(&VmStat) -> u64>(&self, len: usize, counter: F) -> Vec<Sample> {
+            let start = (self.position + 1).saturating_sub(len).max(1);
+            if start > self.position {
+                return Vec::new();
+            }
+            (start..=self.position)
+                .map(|i| {
+                    let (previous, current) = (&self.frames[i - 1], &self.frames[i]);
+                    let value = match (&previous.vmstat, &current.vmstat) {
+                        (Some(before), Some(now)) => {
+                            let secs = (current.timestamp - previous.timestamp)
+                                .to_std()
+                                .unwrap_or_default()
+                                .as_secs_f32();
+                            if secs > 0.0 {
+                                counter(now).saturating_sub(counter(before)) as f32 / secs
+                            } else {
+                                0.0
+                            }
+                        }
+                        _ => 0.0,
+                    };
+                    Sample {
+                        timestamp: current.timestamp,
+                        value,
+                    }
+                })
+                .collect()
+        }
+
+        // Barre de contrôle: lecture/pause, pas à pas, frise chronologique et vitesse.
+        // Renvoie vrai si la position a été modifiée par l'utilisateur.
+        pub fn draw_controls(&mut self, ui: &Ui) -> bool {
+            let mut changed = false;
+            ui.text(&self.title);
+            if ui.button(if self.playing { "Pause" } else { "Play" }) {
+                if !self.playing && !self.live && self.position + 1 >= self.frames.len() {
+                    self.seek(0);
+                    changed = true;
+                }
+                self.playing = !self.playing;
+            }
+            ui.same_line();
+            if ui.button("<") && self.position > 0 {
+                self.seek(self.position - 1);
+                changed = true;
+            }
+            ui.same_line();
+            if ui.button(">") {
+                self.seek(self.position + 1);
+                changed = true;
+            }
+            ui.same_line();
+            ui.set_next_item_width(150.0);
+            ui.slider_config("Speed", 0.25, 16.0)
+                .display_format("%.2fx")
+                .build(&mut self.speed);
+
+            let mut position = self.position;
+            let last = self.frames.len() - 1;
+            ui.set_next_item_width(-1.0);
+            if ui
+                .slider_config("##Timeline", 0, last)
+                .display_format(format!(
+                    "{} ({}/{})",
+                    format_timestamp(self.current().timestamp.into()),
+                    self.position + 1,
+                    self.frames.len()
+                ))
+                .build(&mut position)
+            {
+                self.seek(position);
+                changed = true;
+            }
+            changed
+        }
+    }
+}
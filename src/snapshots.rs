@@ -0,0 +1,239 @@
+pub mod snapshot {
+    use std::{
+        fs, io,
+        path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering as AtomicOrdering},
+            mpsc::{self, Sender},
+            Arc, Mutex,
+        },
+        thread::{self, JoinHandle},
+    };
+
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Serialize};
+
+    use sysinfo::System;
+
+    use crate::{
+        get_process_info, Computer, Cpu, DiskUsage, FanInfo, Interface, MemInfo, Memory, Network,
+        ProcessInfo, Section, SensorReading, VmStat,
+    };
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct CpuSnapshot {
+        pub usage: f32,
+        pub temperature: f32,
+        pub sensors: Vec<SensorReading>,
+        pub fans: Vec<FanInfo>,
+    }
+
+    // État complet de la machine à un instant donné (pièce jointe de rapports de bugs).
+    // Les sections absentes restent à `null`: le format binaire des sessions
+    // (bincode) n'est pas auto-descriptif et ne supporte pas les champs omis.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct Snapshot {
+        pub timestamp: DateTime<Utc>,
+        pub computer: Option<Computer>,
+        pub cpu: Option<CpuSnapshot>,
+        pub memory: Option<Memory>,
+        pub meminfo: Option<MemInfo>,
+        pub vmstat: Option<VmStat>,
+        pub disks: Option<Vec<DiskUsage>>,
+        pub network: Option<Vec<Interface>>,
+        pub processes: Option<Vec<ProcessInfo>>,
+    }
+
+    impl Snapshot {
+        // `top` limite le nombre de processus (triés par mémoire), 0 pour tous
+        pub fn collect(sections: &[Section], top: usize) -> Snapshot {
+            let mut snapshot = Snapshot {
+                timestamp: Utc::now(),
+                computer: None,
+                cpu: None,
+                memory: None,
+                meminfo: None,
+                vmstat: None,
+                disks: None,
+                network: None,
+                processes: None,
+            };
+            for section in sections {
+                match section {
+                    Section::System => snapshot.computer = Some(Computer::new()),
+                    Section::Cpu => {
+                        snapshot.cpu = Some(CpuSnapshot {
+                            usage: Cpu::measure_cpu_usage(),
+                            temperature: Cpu::get_cpu_temperatures(),
+                            sensors: Cpu::get_all_temperatures(),
+                            fans: Cpu::get_all_fan_info().unwrap_or_default(),
+                        })
+                    }
+                    Section::Memory => {
+                        let mut memory = Memory::new();
+                        memory.get_memory();
+                        snapshot.memory = Some(memory);
+                        snapshot.meminfo = MemInfo::read().ok();
+                        snapshot.vmstat = VmStat::read().ok();
+                        snapshot.disks = Some(DiskUsage::list());
+                    }
+                    Section::Network => {
+                        let mut network = Network::new();
+                        network.initialize();
+                        snapshot.network = Some(network.interfaces);
+                    }
+                    Section::Processes => {
+                        let mut processes = get_process_info();
+                        processes.sort_by(|a, b| b.memory_usage.total_cmp(&a.memory_usage));
+                        if top > 0 {
+                            processes.truncate(top);
+                        }
+                        snapshot.processes = Some(processes);
+                    }
+                }
+            }
+            snapshot
+        }
+
+        pub fn collect_all() -> Snapshot {
+            Self::collect(&Section::ALL, 0)
+        }
+
+        pub fn to_json(&self) -> serde_json::Result<String> {
+            serde_json::to_string_pretty(self)
+        }
+
+        pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+            let json = self.to_json().map_err(io::Error::other)?;
+            fs::write(path, json)
+        }
+
+        // Nom de fichier horodaté, ex: snapshot-20240801-153000.json
+        pub fn default_file_name(&self) -> String {
+            format!("snapshot-{}.json", self.timestamp.format("%Y%m%d-%H%M%S"))
+        }
+    }
+
+    // Collecte non bloquante de snapshots complets: l'état sysinfo est conservé
+    // entre deux appels pour mesurer l'utilisation CPU sur l'intervalle écoulé.
+    pub struct SnapshotCollector {
+        system: System,
+        computer: Computer,
+    }
+
+    impl SnapshotCollector {
+        pub fn new() -> SnapshotCollector {
+            let mut system = System::new();
+            system.refresh_cpu_usage();
+            SnapshotCollector {
+                system,
+                computer: Computer::new(),
+            }
+        }
+
+        pub fn collect(&mut self) -> Snapshot {
+            self.system.refresh_cpu_usage();
+            let mut memory = Memory::new();
+            memory.get_memory();
+            let mut network = Network::new();
+            network.initialize();
+            Snapshot {
+                timestamp: Utc::now(),
+                computer: Some(self.computer.clone()),
+                cpu: Some(CpuSnapshot {
+                    usage: self.system.global_cpu_info().cpu_usage(),
+                    temperature: Cpu::get_cpu_temperatures(),
+                    sensors: Cpu::get_all_temperatures(),
+                    fans: Cpu::get_all_fan_info().unwrap_or_default(),
+                }),
+                memory: Some(memory),
+                meminfo: MemInfo::read().ok(),
+                vmstat: VmStat::read().ok(),
+                disks: Some(DiskUsage::list()),
+                network: Some(network.interfaces),
+                processes: Some(get_process_info()),
+            }
+        }
+    }
+
+    impl Default for SnapshotCollector {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    // Snapshots demandés depuis l'interface, enregistrés dans un thread dédié: la
+    // collecte locale mesure le CPU sur un court intervalle et lance `sensors`
+    pub struct SnapshotSaver {
+        busy: Arc<AtomicBool>,
+        status: Arc<Mutex<String>>,
+        sender: Option<Sender<Option<Snapshot>>>,
+        worker: Option<JoinHandle<()>>,
+    }
+
+    impl SnapshotSaver {
+        pub fn new() -> SnapshotSaver {
+            let busy = Arc::new(AtomicBool::new(false));
+            let status = Arc::new(Mutex::new(String::new()));
+            let (sender, receiver) = mpsc::channel::<Option<Snapshot>>();
+            let worker = {
+                let busy = busy.clone();
+                let status = status.clone();
+                thread::spawn(move || {
+                    for snapshot in receiver {
+                        let snapshot = snapshot.unwrap_or_else(Snapshot::collect_all);
+                        let file_name = snapshot.default_file_name();
+                        *status.lock().unwrap() = match snapshot.save(&file_name) {
+                            Ok(()) => format!("Snapshot saved to {}", file_name),
+                            Err(e) => format!("Error saving snapshot: {}", e),
+                        };
+                        busy.store(false, AtomicOrdering::Relaxed);
+                    }
+                })
+            };
+            SnapshotSaver {
+                busy,
+                status,
+                sender: Some(sender),
+                worker: Some(worker),
+            }
+        }
+
+        // `snapshot`: l'état affiché (session relue, hôte distant), sinon la machine
+        // locale est collectée. Ignoré si l'enregistrement précédent n'est pas terminé
+        pub fn save(&self, snapshot: Option<Snapshot>) {
+            if self.busy.swap(true, AtomicOrdering::Relaxed) {
+                return;
+            }
+            *self.status.lock().unwrap() = "Saving snapshot...".to_string();
+            if let Some(sender) = &self.sender {
+                if sender.send(snapshot).is_err() {
+                    self.busy.store(false, AtomicOrdering::Relaxed);
+                }
+            }
+        }
+
+        pub fn is_busy(&self) -> bool {
+            self.busy.load(AtomicOrdering::Relaxed)
+        }
+
+        pub fn status(&self) -> String {
+            self.status.lock().unwrap().clone()
+        }
+    }
+
+    impl Default for SnapshotSaver {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl Drop for SnapshotSaver {
+        fn drop(&mut self) {
+            self.sender.take();
+            if let Some(worker) = self.worker.take() {
+                let _ = worker.join();
+            }
+        }
+    }
+}
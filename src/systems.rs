@@ -1,15 +1,193 @@
 pub mod system {
-    use std::{fs::File, io::{BufRead, BufReader}};
+    use std::{collections::HashSet, fs::{self, File}, io::{BufRead, BufReader}, path::Path};
+    use chrono::{Local, TimeZone, Utc};
     use imgui::Ui;
+    use serde::{Deserialize, Serialize};
     use sysinfo::System;
     use users::{get_current_uid, get_user_by_uid};
 
+    use crate::convert_bytes_to_any;
+
+    pub const DEFAULT_DMI_ROOT: &str = "/sys/class/dmi/id";
+    const CPU_CACHE_ROOT: &str = "/sys/devices/system/cpu/cpu0/cache";
+
+    // Carte mère et constructeur lus dans /sys/class/dmi/id (absents sur ARM et dans
+    // certaines machines virtuelles)
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    pub struct DmiInfo {
+        pub sys_vendor: Option<String>,
+        pub product_name: Option<String>,
+        pub board_vendor: Option<String>,
+        pub board_name: Option<String>,
+        pub bios_version: Option<String>,
+    }
+
+    impl DmiInfo {
+        pub fn read(root: &Path) -> DmiInfo {
+            let field = |name: &str| {
+                fs::read_to_string(root.join(name))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty() && value != "Default string")
+            };
+            DmiInfo {
+                sys_vendor: field("sys_vendor"),
+                product_name: field("product_name"),
+                board_vendor: field("board_vendor"),
+                board_name: field("board_name"),
+                bios_version: field("bios_version"),
+            }
+        }
+    }
+
+    // Topologie et caractéristiques lues dans /proc/cpuinfo
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct CpuInfo {
+        pub physical_cores: Option<usize>,
+        pub sockets: Option<usize>,
+        // Champ "cache size" (dernier niveau), utilisé sans /sys/devices/system/cpu
+        pub cache_size: Option<String>,
+        pub flags: Vec<String>,
+    }
+
+    // Les cœurs physiques sont les couples (physical id, core id) distincts
+    pub fn parse_cpuinfo(content: &str) -> CpuInfo {
+        let mut info = CpuInfo::default();
+        let mut sockets = HashSet::new();
+        let mut cores = HashSet::new();
+        let mut socket = None;
+        for line in content.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "physical id" => {
+                    socket = Some(value.to_string());
+                    sockets.insert(value.to_string());
+                }
+                "core id" => {
+                    cores.insert((socket.clone(), value.to_string()));
+                }
+                "cache size" if info.cache_size.is_none() => {
+                    info.cache_size = Some(value.to_string());
+                }
+                // "Features" sur ARM
+                "flags" | "Features" if info.flags.is_empty() => {
+                    info.flags = value.split_whitespace().map(str::to_string).collect();
+                }
+                _ => {}
+            }
+        }
+        if !sockets.is_empty() {
+            info.sockets = Some(sockets.len());
+        }
+        if !cores.is_empty() {
+            info.physical_cores = Some(cores.len());
+        }
+        info
+    }
+
+    // Caches du premier cœur, ex: "L1d 48K", "L3 32768K"
+    pub fn read_cpu_caches(root: &Path) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(root) else {
+            return Vec::new();
+        };
+        let mut caches: Vec<(String, String)> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("index"))
+            .filter_map(|entry| {
+                let field = |name: &str| {
+                    fs::read_to_string(entry.path().join(name))
+                        .ok()
+                        .map(|value| value.trim().to_string())
+                };
+                let suffix = match field("type")?.as_str() {
+                    "Data" => "d",
+                    "Instruction" => "i",
+                    _ => "",
+                };
+                Some((format!("L{}{}", field("level")?, suffix), field("size")?))
+            })
+            .collect();
+        caches.sort();
+        caches
+            .into_iter()
+            .map(|(name, size)| format!("{} {}", name, size))
+            .collect()
+    }
+
+    // Hyperviseur d'après le drapeau "hypervisor" et le constructeur DMI, ou
+    // conteneur d'après ses fichiers marqueurs
+    pub fn detect_virtualization(flags: &[String], dmi: &DmiInfo, kernel: &str) -> Option<String> {
+        if Path::new("/.dockerenv").exists() {
+            return Some("Docker container".to_string());
+        }
+        if Path::new("/run/.containerenv").exists() {
+            return Some("Podman container".to_string());
+        }
+        if kernel.to_lowercase().contains("microsoft") {
+            return Some("WSL".to_string());
+        }
+        let vendor = format!(
+            "{} {}",
+            dmi.sys_vendor.as_deref().unwrap_or_default(),
+            dmi.product_name.as_deref().unwrap_or_default()
+        );
+        let known = [
+            ("QEMU", "QEMU"),
+            ("KVM", "KVM"),
+            ("VMware", "VMware"),
+            ("VirtualBox", "VirtualBox"),
+            ("Xen", "Xen"),
+            ("Microsoft Corporation Virtual Machine", "Hyper-V"),
+            ("Amazon EC2", "Amazon EC2"),
+            ("Google Compute Engine", "Google Compute Engine"),
+            ("Parallels", "Parallels"),
+        ];
+        if let Some((_, name)) = known.iter().find(|(pattern, _)| vendor.contains(pattern)) {
+            return Some(name.to_string());
+        }
+        if flags.iter().any(|flag| flag == "hypervisor") {
+            return Some("Unknown hypervisor".to_string());
+        }
+        None
+    }
+
+    // Durée au format "3d 04:12:05"
+    pub fn format_uptime(seconds: u64) -> String {
+        let days = seconds / 86400;
+        let time = format!(
+            "{:02}:{:02}:{:02}",
+            seconds % 86400 / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        );
+        if days > 0 {
+            format!("{}d {}", days, time)
+        } else {
+            time
+        }
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct Computer {
         pub cpu_core_count: usize,
         pub cpu_info: String,
         pub os_info: String,
         pub hostname: String,
         pub username: String,
+        pub kernel_version: String,
+        pub architecture: String,
+        // Démarrage de la machine, en secondes depuis l'époque Unix
+        pub boot_time: u64,
+        pub physical_core_count: Option<usize>,
+        pub socket_count: Option<usize>,
+        pub cpu_caches: Vec<String>,
+        pub cpu_flags: Vec<String>,
+        pub virtualization: Option<String>,
+        pub total_memory: u64,
+        pub dmi: DmiInfo,
     }
 
     impl Computer {
@@ -20,6 +198,16 @@ pub mod system {
                 os_info: "N/A".to_string(),
                 hostname: "N/A".to_string(),
                 username: "N/A".to_string(),
+                kernel_version: "N/A".to_string(),
+                architecture: "N/A".to_string(),
+                boot_time: 0,
+                physical_core_count: None,
+                socket_count: None,
+                cpu_caches: Vec::new(),
+                cpu_flags: Vec::new(),
+                virtualization: None,
+                total_memory: 0,
+                dmi: DmiInfo::default(),
             };
             computer.initialize();
             computer
@@ -30,28 +218,122 @@ pub mod system {
             // First we update all information of our `System` struct.
             sys.refresh_all();
             self.cpu_core_count = sys.cpus().len();
-            self.cpu_info = sys.cpus()[0].brand().to_string();
+            // Aucun CPU ni nom d'hôte lisible dans certains conteneurs
+            if let Some(cpu) = sys.cpus().first() {
+                self.cpu_info = cpu.brand().to_string();
+            }
             self.os_info = format!(
                 "{}",
                 get_os_info()
             );
-            self.hostname = System::host_name().unwrap().to_string();
+            self.hostname = System::host_name().unwrap_or_else(|| "N/A".to_string());
             self.username = Self::get_user_name();
+            self.kernel_version = System::kernel_version().unwrap_or_else(|| "N/A".to_string());
+            self.architecture = System::cpu_arch().unwrap_or_else(|| "N/A".to_string());
+            self.boot_time = System::boot_time();
+            self.total_memory = sys.total_memory();
+
+            let cpuinfo = parse_cpuinfo(&fs::read_to_string("/proc/cpuinfo").unwrap_or_default());
+            self.physical_core_count = cpuinfo.physical_cores.or_else(|| sys.physical_core_count());
+            self.socket_count = cpuinfo.sockets;
+            self.cpu_caches = read_cpu_caches(Path::new(CPU_CACHE_ROOT));
+            if self.cpu_caches.is_empty() {
+                self.cpu_caches.extend(cpuinfo.cache_size);
+            }
+            self.dmi = DmiInfo::read(Path::new(DEFAULT_DMI_ROOT));
+            self.virtualization = detect_virtualization(&cpuinfo.flags, &self.dmi, &self.kernel_version);
+            self.cpu_flags = cpuinfo.flags;
         }
         pub fn get_user_name() -> String {
             match get_user_by_uid(get_current_uid()) {
-                Some(user) => user.name().to_str().unwrap().to_string(),
+                Some(user) => user.name().to_string_lossy().to_string(),
                 None => "N/A".to_string(),
             }
         }
+
+        // Temps écoulé depuis le démarrage, calculé à l'affichage
+        pub fn uptime(&self) -> u64 {
+            (Utc::now().timestamp().max(0) as u64).saturating_sub(self.boot_time)
+        }
+
+        // Lignes communes à la fenêtre, au rapport texte et au TUI (sans les drapeaux CPU)
+        pub fn summary_lines(&self) -> Vec<String> {
+            let or_na = |value: Option<String>| value.unwrap_or_else(|| "N/A".to_string());
+            let mut lines = vec![
+                format!("Computer Name: {}", self.hostname),
+                format!("User Name: {}", self.username),
+                format!("OS Info: {}", self.os_info),
+                format!("Kernel: {} ({})", self.kernel_version, self.architecture),
+            ];
+            if self.boot_time > 0 {
+                let boot = Local
+                    .timestamp_opt(self.boot_time as i64, 0)
+                    .single()
+                    .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string());
+                lines.push(format!("Boot Time: {}", or_na(boot)));
+                lines.push(format!("Uptime: {}", format_uptime(self.uptime())));
+            }
+            lines.push(format!("CPU Info: {}", self.cpu_info));
+            lines.push(format!("CPU Core Count: {}", self.cpu_core_count));
+            lines.push(format!(
+                "Physical Cores: {}   Sockets: {}",
+                or_na(self.physical_core_count.map(|count| count.to_string())),
+                or_na(self.socket_count.map(|count| count.to_string()))
+            ));
+            if !self.cpu_caches.is_empty() {
+                lines.push(format!("CPU Caches: {}", self.cpu_caches.join(", ")));
+            }
+            lines.push(format!("Total Memory: {}", convert_bytes_to_any(self.total_memory)));
+            lines.push(format!(
+                "Virtualization: {}",
+                self.virtualization.as_deref().unwrap_or("none")
+            ));
+            let join = |a: &Option<String>, b: &Option<String>| match (a, b) {
+                (None, None) => None,
+                _ => Some(
+                    [a, b]
+                        .iter()
+                        .filter_map(|value| value.as_deref())
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                ),
+            };
+            if let Some(system) = join(&self.dmi.sys_vendor, &self.dmi.product_name) {
+                lines.push(format!("Machine: {}", system));
+            }
+            if let Some(board) = join(&self.dmi.board_vendor, &self.dmi.board_name) {
+                lines.push(format!("Board: {}", board));
+            }
+            if let Some(bios) = &self.dmi.bios_version {
+                lines.push(format!("BIOS: {}", bios));
+            }
+            lines
+        }
+
+        // Rapport complet, copié dans le presse-papiers
+        pub fn report(&self) -> String {
+            let mut lines = self.summary_lines();
+            if !self.cpu_flags.is_empty() {
+                lines.push(format!("CPU Flags: {}", self.cpu_flags.join(" ")));
+            }
+            lines.join("\n") + "\n"
+        }
+
         pub fn display(&self,ui: &Ui){
             // Affichage des informations dans la fenêtre
             ui.text("System Information:");
-            ui.text(format!("Computer Name: {}", self.hostname));
-            ui.text(format!("User Name: {}", self.username));
-            ui.text(format!("OS Info: {}", self.os_info));
-            ui.text(format!("CPU Info: {}", self.cpu_info));
-            ui.text(format!("CPU Core Count: {}", self.cpu_core_count));
+            ui.same_line();
+            if ui.small_button("Copy to clipboard") {
+                ui.set_clipboard_text(self.report());
+            }
+            for line in self.summary_lines() {
+                ui.text(line);
+            }
+            if !self.cpu_flags.is_empty() {
+                if let Some(_node) = ui.tree_node(format!("CPU Flags ({})", self.cpu_flags.len())) {
+                    ui.text_wrapped(self.cpu_flags.join(" "));
+                }
+            }
             ui.text("\n");
         }
     }
@@ -0,0 +1,334 @@
+pub mod theme {
+    use std::{collections::BTreeMap, fmt};
+
+    use imgui::{Style, StyleColor};
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub const DEFAULT_THEME: &str = "dark";
+    pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "classic", "high-contrast"];
+
+    // Couleur RGBA; dans les fichiers: "#rrggbb", "#rrggbbaa" ou [r, g, b(, a)] entre 0 et 1
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct ThemeColor(pub [f32; 4]);
+
+    impl ThemeColor {
+        pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> ThemeColor {
+            ThemeColor([r, g, b, a])
+        }
+
+        pub fn parse_hex(text: &str) -> Result<ThemeColor, String> {
+            let hex = text
+                .strip_prefix('#')
+                .ok_or_else(|| format!("invalid color '{}'", text))?;
+            if !matches!(hex.len(), 6 | 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!("invalid color '{}'", text));
+            }
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap() as f32 / 255.0;
+            let alpha = if hex.len() == 8 { channel(6) } else { 1.0 };
+            Ok(ThemeColor([channel(0), channel(2), channel(4), alpha]))
+        }
+    }
+
+    impl fmt::Display for ThemeColor {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let [r, g, b, a] = self.0.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            write!(f, "#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        }
+    }
+
+    impl Serialize for ThemeColor {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.to_string())
+        }
+    }
+
+    impl<'de> Deserialize<'de> for ThemeColor {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            #[derive(Deserialize)]
+            #[serde(untagged)]
+            enum Raw {
+                Hex(String),
+                Channels(Vec<f32>),
+            }
+            match Raw::deserialize(deserializer)? {
+                Raw::Hex(text) => ThemeColor::parse_hex(&text).map_err(de::Error::custom),
+                Raw::Channels(channels) => match channels[..] {
+                    [r, g, b] => Ok(ThemeColor([r, g, b, 1.0])),
+                    [r, g, b, a] => Ok(ThemeColor([r, g, b, a])),
+                    _ => Err(de::Error::custom("a color needs 3 or 4 channels")),
+                },
+            }
+        }
+    }
+
+    // Couleurs des niveaux de seuil (barres réseau, jauges)
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct Palette {
+        pub good: ThemeColor,
+        pub warning: ThemeColor,
+        pub critical: ThemeColor,
+    }
+
+    impl Default for Palette {
+        fn default() -> Self {
+            Palette {
+                good: ThemeColor::rgba(0.0, 1.0, 0.0, 1.0),
+                warning: ThemeColor::rgba(1.0, 1.0, 0.0, 1.0),
+                critical: ThemeColor::rgba(1.0, 0.0, 0.0, 1.0),
+            }
+        }
+    }
+
+    // Thème défini par l'utilisateur dans config.toml ([themes.<nom>]);
+    // les champs absents viennent du thème `base`
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    pub struct ThemeDefinition {
+        pub base: Option<String>,
+        pub window_rounding: Option<f32>,
+        pub frame_rounding: Option<f32>,
+        pub window_border_size: Option<f32>,
+        pub frame_border_size: Option<f32>,
+        pub graph_lines: Option<Vec<ThemeColor>>,
+        pub marker: Option<ThemeColor>,
+        pub palette: Option<Palette>,
+        // Couleurs ImGui par nom: "window_bg", "text", "plot_lines"...
+        pub colors: BTreeMap<String, ThemeColor>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum BaseColors {
+        Dark,
+        Light,
+        Classic,
+    }
+
+    // Thème résolu, prêt à être appliqué
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Theme {
+        pub name: String,
+        base: BaseColors,
+        pub window_rounding: f32,
+        pub frame_rounding: f32,
+        pub window_border_size: f32,
+        pub frame_border_size: f32,
+        // Couleurs des séries de graphiques, la première pour les graphiques simples
+        pub graph_lines: Vec<ThemeColor>,
+        pub marker: ThemeColor,
+        pub palette: Palette,
+        pub colors: Vec<(StyleColor, ThemeColor)>,
+    }
+
+    impl Default for Theme {
+        fn default() -> Self {
+            Theme::builtin(DEFAULT_THEME).unwrap()
+        }
+    }
+
+    // "window_bg" ou "WindowBg" -> StyleColor::WindowBg
+    pub fn style_color(name: &str) -> Option<StyleColor> {
+        let wanted = name.replace(['_', '-'], "").to_lowercase();
+        StyleColor::VARIANTS
+            .into_iter()
+            .find(|color| color.name().to_lowercase() == wanted)
+    }
+
+    impl Theme {
+        pub fn builtin(name: &str) -> Option<Theme> {
+            let mut theme = Theme {
+                name: name.to_string(),
+                base: BaseColors::Dark,
+                window_rounding: 0.0,
+                frame_rounding: 0.0,
+                window_border_size: 1.0,
+                frame_border_size: 0.0,
+                graph_lines: vec![
+                    ThemeColor::rgba(0.61, 0.61, 0.61, 1.0),
+                    ThemeColor::rgba(0.26, 0.59, 0.98, 1.0),
+                    ThemeColor::rgba(1.0, 0.6, 0.0, 1.0),
+                    ThemeColor::rgba(0.4, 0.8, 0.4, 1.0),
+                    ThemeColor::rgba(0.8, 0.4, 0.8, 1.0),
+                ],
+                marker: ThemeColor::rgba(1.0, 0.3, 0.3, 1.0),
+                palette: Palette::default(),
+                colors: Vec::new(),
+            };
+            match name {
+                "dark" => {}
+                "light" => {
+                    theme.base = BaseColors::Light;
+                    theme.graph_lines[0] = ThemeColor::rgba(0.39, 0.39, 0.39, 1.0);
+                    theme.marker = ThemeColor::rgba(0.85, 0.1, 0.1, 1.0);
+                    theme.palette = Palette {
+                        good: ThemeColor::rgba(0.1, 0.6, 0.1, 1.0),
+                        warning: ThemeColor::rgba(0.85, 0.6, 0.0, 1.0),
+                        critical: ThemeColor::rgba(0.85, 0.1, 0.1, 1.0),
+                    };
+                }
+                "classic" => theme.base = BaseColors::Classic,
+                "high-contrast" => {
+                    let black = ThemeColor::rgba(0.0, 0.0, 0.0, 1.0);
+                    let white = ThemeColor::rgba(1.0, 1.0, 1.0, 1.0);
+                    let yellow = ThemeColor::rgba(1.0, 1.0, 0.0, 1.0);
+                    let cyan = ThemeColor::rgba(0.0, 1.0, 1.0, 1.0);
+                    let dim = ThemeColor::rgba(0.25, 0.25, 0.25, 1.0);
+                    theme.window_border_size = 2.0;
+                    theme.frame_border_size = 1.0;
+                    theme.graph_lines =
+                        vec![yellow, cyan, ThemeColor::rgba(1.0, 0.0, 1.0, 1.0), white];
+                    theme.marker = ThemeColor::rgba(1.0, 0.0, 0.0, 1.0);
+                    theme.palette = Palette {
+                        good: cyan,
+                        warning: yellow,
+                        critical: ThemeColor::rgba(1.0, 0.0, 1.0, 1.0),
+                    };
+                    theme.colors = vec![
+                        (StyleColor::Text, white),
+                        (
+                            StyleColor::TextDisabled,
+                            ThemeColor::rgba(0.7, 0.7, 0.7, 1.0),
+                        ),
+                        (StyleColor::WindowBg, black),
+                        (StyleColor::ChildBg, black),
+                        (StyleColor::PopupBg, black),
+                        (StyleColor::Border, white),
+                        (StyleColor::FrameBg, black),
+                        (StyleColor::FrameBgHovered, dim),
+                        (StyleColor::FrameBgActive, dim),
+                        (StyleColor::TitleBg, black),
+                        (
+                            StyleColor::TitleBgActive,
+                            ThemeColor::rgba(0.0, 0.0, 0.6, 1.0),
+                        ),
+                        (StyleColor::TitleBgCollapsed, black),
+                        (StyleColor::Button, black),
+                        (
+                            StyleColor::ButtonHovered,
+                            ThemeColor::rgba(0.0, 0.0, 0.6, 1.0),
+                        ),
+                        (
+                            StyleColor::ButtonActive,
+                            ThemeColor::rgba(0.0, 0.0, 0.8, 1.0),
+                        ),
+                        (StyleColor::Header, ThemeColor::rgba(0.0, 0.0, 0.6, 1.0)),
+                        (
+                            StyleColor::HeaderHovered,
+                            ThemeColor::rgba(0.0, 0.0, 0.8, 1.0),
+                        ),
+                        (
+                            StyleColor::HeaderActive,
+                            ThemeColor::rgba(0.0, 0.0, 1.0, 1.0),
+                        ),
+                        (StyleColor::Tab, black),
+                        (StyleColor::TabHovered, ThemeColor::rgba(0.0, 0.0, 0.8, 1.0)),
+                        (StyleColor::TabActive, ThemeColor::rgba(0.0, 0.0, 0.6, 1.0)),
+                        (StyleColor::CheckMark, yellow),
+                        (StyleColor::SliderGrab, yellow),
+                        (StyleColor::SliderGrabActive, white),
+                        (StyleColor::Separator, white),
+                        (StyleColor::PlotHistogram, yellow),
+                        (StyleColor::PlotHistogramHovered, white),
+                    ];
+                }
+                _ => return None,
+            }
+            Some(theme)
+        }
+
+        // Thème intégré ou défini par l'utilisateur (prioritaire en cas de nom identique)
+        pub fn resolve(
+            name: &str,
+            user_themes: &BTreeMap<String, ThemeDefinition>,
+        ) -> Result<Theme, String> {
+            let Some(definition) = user_themes.get(name) else {
+                return Theme::builtin(name).ok_or_else(|| {
+                    format!(
+                        "unknown theme '{}' (available: {})",
+                        name,
+                        theme_names(user_themes).join(", ")
+                    )
+                });
+            };
+            let base = definition.base.as_deref().unwrap_or(DEFAULT_THEME);
+            let mut theme = Theme::builtin(base).ok_or_else(|| {
+                format!(
+                    "theme '{}': unknown base '{}' (expected one of {})",
+                    name,
+                    base,
+                    BUILTIN_THEMES.join(", ")
+                )
+            })?;
+            theme.name = name.to_string();
+            if let Some(rounding) = definition.window_rounding {
+                theme.window_rounding = rounding;
+            }
+            if let Some(rounding) = definition.frame_rounding {
+                theme.frame_rounding = rounding;
+            }
+            if let Some(size) = definition.window_border_size {
+                theme.window_border_size = size;
+            }
+            if let Some(size) = definition.frame_border_size {
+                theme.frame_border_size = size;
+            }
+            if let Some(lines) = &definition.graph_lines {
+                if lines.is_empty() {
+                    return Err(format!("theme '{}': graph_lines is empty", name));
+                }
+                theme.graph_lines = lines.clone();
+            }
+            if let Some(marker) = definition.marker {
+                theme.marker = marker;
+            }
+            if let Some(palette) = definition.palette {
+                theme.palette = palette;
+            }
+            for (color_name, color) in &definition.colors {
+                let style_color = style_color(color_name)
+                    .ok_or_else(|| format!("theme '{}': unknown color '{}'", name, color_name))?;
+                theme
+                    .colors
+                    .retain(|(existing, _)| *existing != style_color);
+                theme.colors.push((style_color, *color));
+            }
+            Ok(theme)
+        }
+
+        pub fn apply(&self, style: &mut Style) {
+            match self.base {
+                BaseColors::Dark => style.use_dark_colors(),
+                BaseColors::Light => style.use_light_colors(),
+                BaseColors::Classic => style.use_classic_colors(),
+            };
+            style.window_rounding = self.window_rounding;
+            style.child_rounding = self.window_rounding;
+            style.popup_rounding = self.window_rounding;
+            style.frame_rounding = self.frame_rounding;
+            style.grab_rounding = self.frame_rounding;
+            style.tab_rounding = self.frame_rounding;
+            style.scrollbar_rounding = self.frame_rounding;
+            style.window_border_size = self.window_border_size;
+            style.frame_border_size = self.frame_border_size;
+            style[StyleColor::PlotLines] = self.graph_line(0);
+            for (color, value) in &self.colors {
+                style[*color] = value.0;
+            }
+        }
+
+        // Couleur de la série `index`, la liste est parcourue en boucle
+        pub fn graph_line(&self, index: usize) -> [f32; 4] {
+            self.graph_lines[index % self.graph_lines.len()].0
+        }
+    }
+
+    // Thèmes intégrés puis thèmes de l'utilisateur
+    pub fn theme_names(user_themes: &BTreeMap<String, ThemeDefinition>) -> Vec<String> {
+        let mut names: Vec<String> = BUILTIN_THEMES.iter().map(|name| name.to_string()).collect();
+        for name in user_themes.keys() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+}
@@ -0,0 +1,326 @@
+pub mod terminal {
+    use std::{
+        io,
+        time::{Duration, Instant},
+    };
+
+    use ratatui::{
+        crossterm::event::{self, Event, KeyCode, KeyEventKind},
+        layout::{Constraint, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::{Block, Cell, Gauge, Paragraph, Row, Sparkline, Table, TableState, Tabs},
+        DefaultTerminal, Frame,
+    };
+    use sysinfo::System;
+
+    use crate::{
+        convert_bytes_to_any, get_process_info, Computer, Cpu, GraphData, MemInfo, Memory,
+        Network, ProcessInfo,
+    };
+
+    const TABS: [&str; 4] = ["System", "Memory and Processes", "Graphics", "Network"];
+
+    pub fn run_tui() -> io::Result<()> {
+        let mut terminal = ratatui::init();
+        let result = App::new().run(&mut terminal);
+        ratatui::restore();
+        result
+    }
+
+    struct App {
+        tab: usize,
+        computer: Computer,
+        system: System,
+        memory: Memory,
+        meminfo: MemInfo,
+        network: Network,
+        processes: Vec<ProcessInfo>,
+        process_state: TableState,
+        cpu_graph: GraphData,
+        ram_graph: GraphData,
+        swap_graph: GraphData,
+        temp_graph: GraphData,
+        last_refresh: Instant,
+        refresh_interval: Duration,
+    }
+
+    impl App {
+        fn new() -> App {
+            let mut app = App {
+                tab: 0,
+                computer: Computer::new(),
+                system: System::new(),
+                memory: Memory::new(),
+                meminfo: MemInfo::default(),
+                network: Network::new(),
+                processes: Vec::new(),
+                process_state: TableState::default().with_selected(0),
+                cpu_graph: GraphData::new(100, Duration::from_secs(1)),
+                ram_graph: GraphData::new(100, Duration::from_secs(1)),
+                swap_graph: GraphData::new(100, Duration::from_secs(1)),
+                temp_graph: GraphData::new(100, Duration::from_secs(1)),
+                last_refresh: Instant::now(),
+                refresh_interval: Duration::from_secs(1),
+            };
+            app.refresh();
+            app
+        }
+
+        fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+            loop {
+                terminal.draw(|frame| self.draw(frame))?;
+
+                if event::poll(Duration::from_millis(250))? {
+                    if let Event::Key(key) = event::read()? {
+                        if key.kind != KeyEventKind::Press {
+                            continue;
+                        }
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                            KeyCode::Tab | KeyCode::Right => self.tab = (self.tab + 1) % TABS.len(),
+                            KeyCode::BackTab | KeyCode::Left => {
+                                self.tab = (self.tab + TABS.len() - 1) % TABS.len()
+                            }
+                            KeyCode::Char(c @ '1'..='4') => self.tab = c as usize - '1' as usize,
+                            KeyCode::Char('p') => self.toggle_pause(),
+                            KeyCode::Down | KeyCode::Char('j') => self.process_state.select_next(),
+                            KeyCode::Up | KeyCode::Char('k') => self.process_state.select_previous(),
+                            KeyCode::PageDown => self.process_state.scroll_down_by(10),
+                            KeyCode::PageUp => self.process_state.scroll_up_by(10),
+                            _ => {}
+                        }
+                    }
+                }
+
+                if self.last_refresh.elapsed() >= self.refresh_interval {
+                    self.refresh();
+                }
+            }
+        }
+
+        fn toggle_pause(&mut self) {
+            let paused = !self.cpu_graph.is_paused;
+            for graph in [
+                &mut self.cpu_graph,
+                &mut self.ram_graph,
+                &mut self.swap_graph,
+                &mut self.temp_graph,
+            ] {
+                graph.is_paused = paused;
+            }
+        }
+
+        fn refresh(&mut self) {
+            self.last_refresh = Instant::now();
+            self.system.refresh_cpu_usage();
+            self.memory.get_memory();
+            self.meminfo = MemInfo::read().unwrap_or_default();
+            self.network = Network::new();
+            self.network.initialize();
+            self.processes = get_process_info();
+            self.processes
+                .sort_by(|a, b| b.memory_usage.total_cmp(&a.memory_usage));
+
+            self.cpu_graph
+                .update(self.system.global_cpu_info().cpu_usage());
+            self.ram_graph.update(self.meminfo.used_percent());
+            self.swap_graph.update(self.meminfo.swap_used_percent());
+            self.temp_graph.update(Cpu::get_cpu_temperatures());
+        }
+
+        fn draw(&mut self, frame: &mut Frame) {
+            let [header, body, footer] = Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .areas(frame.area());
+
+            let tabs = Tabs::new(TABS)
+                .block(Block::bordered().title(format!(" {} ", self.computer.hostname)))
+                .select(self.tab)
+                .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+            frame.render_widget(tabs, header);
+
+            match self.tab {
+                0 => self.draw_system(frame, body),
+                1 => self.draw_memory(frame, body),
+                2 => self.draw_graphics(frame, body),
+                _ => self.draw_network(frame, body),
+            }
+
+            let pause = if self.cpu_graph.is_paused { "resume" } else { "pause" };
+            frame.render_widget(
+                Paragraph::new(format!(
+                    " q: quit  ←/→ 1-4: tabs  ↑/↓ PgUp/PgDn: scroll  p: {}",
+                    pause
+                ))
+                .style(Style::default().fg(Color::DarkGray)),
+                footer,
+            );
+        }
+
+        fn draw_system(&self, frame: &mut Frame, area: Rect) {
+            let computer = &self.computer;
+            let lines: Vec<Line> = computer.summary_lines().into_iter().map(Line::from).collect();
+            frame.render_widget(
+                Paragraph::new(lines).block(Block::bordered().title(" System Information ")),
+                area,
+            );
+        }
+
+        fn draw_memory(&mut self, frame: &mut Frame, area: Rect) {
+            let [ram, swap, storage, breakdown, processes] = Layout::vertical([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .areas(area);
+
+            let memory = &self.memory;
+            let gauges = [
+                ("RAM", memory.ram.used_ram, memory.ram.total_ram, ram),
+                ("Swap", memory.swap.used_swap, memory.swap.total_swap, swap),
+                ("Storage", memory.storage.used_disk, memory.storage.total_disk, storage),
+            ];
+            for (name, used, total, area) in gauges {
+                let ratio = if total > 0 { used as f64 / total as f64 } else { 0.0 };
+                let gauge = Gauge::default()
+                    .block(Block::bordered().title(format!(" {} ", name)))
+                    .gauge_style(Style::default().fg(usage_color(ratio)))
+                    .ratio(ratio.clamp(0.0, 1.0))
+                    .label(format!(
+                        "{} / {}",
+                        convert_bytes_to_any(used),
+                        convert_bytes_to_any(total)
+                    ));
+                frame.render_widget(gauge, area);
+            }
+
+            let spans: Vec<Span> = self
+                .meminfo
+                .segments()
+                .iter()
+                .map(|(name, bytes, color)| {
+                    Span::styled(
+                        format!(" {} {} ", name, convert_bytes_to_any(*bytes)),
+                        Style::default().fg(rgb(*color)),
+                    )
+                })
+                .collect();
+            frame.render_widget(Paragraph::new(Line::from(spans)), breakdown);
+
+            let rows = self.processes.iter().map(|process| {
+                Row::new(vec![
+                    Cell::from(process.pid.to_string()),
+                    Cell::from(process.name.clone()),
+                    Cell::from(process.state.clone()),
+                    Cell::from(format!("{:.2}%", process.cpu_usage)),
+                    Cell::from(format!("{:.2}%", process.memory_usage)),
+                ])
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(8),
+                    Constraint::Min(20),
+                    Constraint::Length(6),
+                    Constraint::Length(10),
+                    Constraint::Length(12),
+                ],
+            )
+            .header(
+                Row::new(["PID", "Name", "State", "CPU Usage", "Memory Usage"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::bordered().title(format!(
+                " Processes ({}) ",
+                self.processes.len()
+            )))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            frame.render_stateful_widget(table, processes, &mut self.process_state);
+        }
+
+        fn draw_graphics(&self, frame: &mut Frame, area: Rect) {
+            let areas = Layout::vertical([Constraint::Ratio(1, 4); 4]).split(area);
+            let graphs = [
+                ("CPU Usage", &self.cpu_graph, "%", Some(100)),
+                ("RAM Usage", &self.ram_graph, "%", Some(100)),
+                ("Swap Usage", &self.swap_graph, "%", Some(100)),
+                ("CPU Temperature", &self.temp_graph, "°C", None),
+            ];
+            for ((label, graph, unit, max), area) in graphs.into_iter().zip(areas.iter()) {
+                let values = graph.values();
+                let last = graph.last_value().unwrap_or(0.0);
+                // Afficher les derniers échantillons qui tiennent dans la largeur
+                let width = area.width.saturating_sub(2) as usize;
+                let data: Vec<u64> = values
+                    .iter()
+                    .skip(values.len().saturating_sub(width))
+                    .map(|v| v.max(0.0).round() as u64)
+                    .collect();
+                let mut sparkline = Sparkline::default()
+                    .block(Block::bordered().title(format!(" {}: {:.2}{} ", label, last, unit)))
+                    .data(&data)
+                    .style(Style::default().fg(Color::Cyan));
+                if let Some(max) = max {
+                    sparkline = sparkline.max(max);
+                }
+                frame.render_widget(sparkline, *area);
+            }
+        }
+
+        fn draw_network(&self, frame: &mut Frame, area: Rect) {
+            let rows = self.network.interfaces.iter().map(|interface| {
+                let rx_packets = interface.rx_stats.as_ref().map_or(0, |s| s.packets);
+                let tx_packets = interface.tx_stats.as_ref().map_or(0, |s| s.packets);
+                Row::new(vec![
+                    interface.name.clone(),
+                    interface.ip.to_string(),
+                    convert_bytes_to_any(interface.total_received),
+                    rx_packets.to_string(),
+                    convert_bytes_to_any(interface.total_transmitted),
+                    tx_packets.to_string(),
+                ])
+            });
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Min(12),
+                    Constraint::Length(16),
+                    Constraint::Length(12),
+                    Constraint::Length(12),
+                    Constraint::Length(12),
+                    Constraint::Length(12),
+                ],
+            )
+            .header(
+                Row::new(["Interface", "IP", "RX", "RX Packets", "TX", "TX Packets"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .block(Block::bordered().title(" Network "));
+            frame.render_widget(table, area);
+        }
+    }
+
+    fn rgb(color: [f32; 4]) -> Color {
+        Color::Rgb(
+            (color[0] * 255.0) as u8,
+            (color[1] * 255.0) as u8,
+            (color[2] * 255.0) as u8,
+        )
+    }
+
+    fn usage_color(ratio: f64) -> Color {
+        if ratio <= 0.5 {
+            Color::Green
+        } else if ratio <= 2.0 / 3.0 {
+            Color::Yellow
+        } else {
+            Color::Red
+        }
+    }
+}